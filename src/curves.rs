@@ -0,0 +1,458 @@
+use crate::elliptic_curve::{
+    DomainParameters, EllipticCurve, GlvEndomorphism, MontgomeryCurve, Point, TwistedEdwardsCurve,
+};
+use num_bigint::{BigInt, BigUint};
+use std::sync::OnceLock;
+
+// Named prime curves from SEC 2, FIPS 186-4 and RFC 5639. Parameters are
+// parsed and validated on first use and cached for the life of the process.
+struct NamedCurve {
+    name: &'static str,
+    aliases: &'static [&'static str],
+    oid: &'static str,
+    p: &'static str,
+    a: &'static str,
+    b: &'static str,
+    gx: &'static str,
+    gy: &'static str,
+    n: &'static str,
+    h: u32,
+    glv: Option<Glv>,
+}
+
+// GLV endomorphism constants: beta mod p, lambda mod n and the lattice
+// basis v1 = (a1, b1), v2 = (a2, b2), in signed hex
+struct Glv {
+    beta: &'static str,
+    lambda: &'static str,
+    a1: &'static str,
+    b1: &'static str,
+    a2: &'static str,
+    b2: &'static str,
+}
+
+const P384: &str = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFF0000000000000000FFFFFFFF";
+const P521: &str = "01FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF";
+
+static CURVES: [NamedCurve; 8] = [
+    NamedCurve {
+        name: "secp256k1",
+        aliases: &[],
+        oid: "1.3.132.0.10",
+        p: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+        a: "00",
+        b: "07",
+        gx: "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        gy: "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+        n: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+        h: 1,
+        glv: Some(Glv {
+            beta: "7AE96A2B657C07106E64479EAC3434E99CF0497512F58995C1396C28719501EE",
+            lambda: "5363AD4CC05C30E0A5261C028812645A122E22EA20816678DF02967C1B23BD72",
+            a1: "3086D221A7D46BCDE86C90E49284EB15",
+            b1: "-E4437ED6010E88286F547FA90ABFE4C3",
+            a2: "114CA50F7A8E2F3F657C1108D9D44CFD8",
+            b2: "3086D221A7D46BCDE86C90E49284EB15",
+        }),
+    },
+    NamedCurve {
+        name: "secp224r1",
+        aliases: &["P-224"],
+        oid: "1.3.132.0.33",
+        p: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF000000000000000000000001",
+        a: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFE",
+        b: "B4050A850C04B3ABF54132565044B0B7D7BFD8BA270B39432355FFB4",
+        gx: "B70E0CBD6BB4BF7F321390B94A03C1D356C21122343280D6115C1D21",
+        gy: "BD376388B5F723FB4C22DFE6CD4375A05A07476444D5819985007E34",
+        n: "FFFFFFFFFFFFFFFFFFFFFFFFFFFF16A2E0B8F03E13DD29455C5C2A3D",
+        h: 1,
+        glv: None,
+    },
+    NamedCurve {
+        name: "secp256r1",
+        aliases: &["P-256", "prime256v1"],
+        oid: "1.2.840.10045.3.1.7",
+        p: "FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF",
+        a: "FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFC",
+        b: "5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B",
+        gx: "6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296",
+        gy: "4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5",
+        n: "FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551",
+        h: 1,
+        glv: None,
+    },
+    NamedCurve {
+        name: "secp384r1",
+        aliases: &["P-384"],
+        oid: "1.3.132.0.34",
+        p: P384,
+        a: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFF0000000000000000FFFFFFFC",
+        b: "B3312FA7E23EE7E4988E056BE3F82D19181D9C6EFE8141120314088F5013875AC656398D8A2ED19D2A85C8EDD3EC2AEF",
+        gx: "AA87CA22BE8B05378EB1C71EF320AD746E1D3B628BA79B9859F741E082542A385502F25DBF55296C3A545E3872760AB7",
+        gy: "3617DE4A96262C6F5D9E98BF9292DC29F8F41DBD289A147CE9DA3113B5F0B8C00A60B1CE1D7E819D7A431D7C90EA0E5F",
+        n: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFC7634D81F4372DDF581A0DB248B0A77AECEC196ACCC52973",
+        h: 1,
+        glv: None,
+    },
+    NamedCurve {
+        name: "secp521r1",
+        aliases: &["P-521"],
+        oid: "1.3.132.0.35",
+        p: P521,
+        a: "01FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFC",
+        b: "0051953EB9618E1C9A1F929A21A0B68540EEA2DA725B99B315F3B8B489918EF109E156193951EC7E937B1652C0BD3BB1BF073573DF883D2C34F1EF451FD46B503F00",
+        gx: "00C6858E06B70404E9CD9E3ECB662395B4429C648139053FB521F828AF606B4D3DBAA14B5E77EFE75928FE1DC127A2FFA8DE3348B3C1856A429BF97E7E31C2E5BD66",
+        gy: "011839296A789A3BC0045C8A5FB42C7D1BD998F54449579B446817AFBD17273E662C97EE72995EF42640C550B9013FAD0761353C7086A272C24088BE94769FD16650",
+        n: "01FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFA51868783BF2F966B7FCC0148F709A5D03BB5C9B8899C47AEBB6FB71E91386409",
+        h: 1,
+        glv: None,
+    },
+    NamedCurve {
+        name: "brainpoolP256r1",
+        aliases: &[],
+        oid: "1.3.36.3.3.2.8.1.1.7",
+        p: "A9FB57DBA1EEA9BC3E660A909D838D726E3BF623D52620282013481D1F6E5377",
+        a: "7D5A0975FC2C3057EEF67530417AFFE7FB8055C126DC5C6CE94A4B44F330B5D9",
+        b: "26DC5C6CE94A4B44F330B5D9BBD77CBF958416295CF7E1CE6BCCDC18FF8C07B6",
+        gx: "8BD2AEB9CB7E57CB2C4B482FFC81B7AFB9DE27E1E3BD23C23A4453BD9ACE3262",
+        gy: "547EF835C3DAC4FD97F8461A14611DC9C27745132DED8E545C1D54C72F046997",
+        n: "A9FB57DBA1EEA9BC3E660A909D838D718C397AA3B561A6F7901E0E82974856A7",
+        h: 1,
+        glv: None,
+    },
+    NamedCurve {
+        name: "brainpoolP384r1",
+        aliases: &[],
+        oid: "1.3.36.3.3.2.8.1.1.11",
+        p: "8CB91E82A3386D280F5D6F7E50E641DF152F7109ED5456B412B1DA197FB71123ACD3A729901D1A71874700133107EC53",
+        a: "7BC382C63D8C150C3C72080ACE05AFA0C2BEA28E4FB22787139165EFBA91F90F8AA5814A503AD4EB04A8C7DD22CE2826",
+        b: "04A8C7DD22CE28268B39B55416F0447C2FB77DE107DCD2A62E880EA53EEB62D57CB4390295DBC9943AB78696FA504C11",
+        gx: "1D1C64F068CF45FFA2A63A81B7C13F6B8847A3E77EF14FE3DB7FCAFE0CBD10E8E826E03436D646AAEF87B2E247D4AF1E",
+        gy: "8ABE1D7520F9C2A45CB1EB8E95CFD55262B70B29FEEC5864E19C054FF99129280E4646217791811142820341263C5315",
+        n: "8CB91E82A3386D280F5D6F7E50E641DF152F7109ED5456B31F166E6CAC0425A7CF3AB6AF6B7FC3103B883202E9046565",
+        h: 1,
+        glv: None,
+    },
+    NamedCurve {
+        name: "brainpoolP512r1",
+        aliases: &[],
+        oid: "1.3.36.3.3.2.8.1.1.13",
+        p: "AADD9DB8DBE9C48B3FD4E6AE33C9FC07CB308DB3B3C9D20ED6639CCA703308717D4D9B009BC66842AECDA12AE6A380E62881FF2F2D82C68528AA6056583A48F3",
+        a: "7830A3318B603B89E2327145AC234CC594CBDD8D3DF91610A83441CAEA9863BC2DED5D5AA8253AA10A2EF1C98B9AC8B57F1117A72BF2C7B9E7C1AC4D77FC94CA",
+        b: "3DF91610A83441CAEA9863BC2DED5D5AA8253AA10A2EF1C98B9AC8B57F1117A72BF2C7B9E7C1AC4D77FC94CADC083E67984050B75EBAE5DD2809BD638016F723",
+        gx: "81AEE4BDD82ED9645A21322E9C4C6A9385ED9F70B5D916C1B43B62EEF4D0098EFF3B1F78E2D0D48D50D1687B93B97D5F7C6D5047406A5E688B352209BCB9F822",
+        gy: "7DDE385D566332ECC0EABFA9CF7822FDF209F70024A57B1AA000C55B881F8111B2DCDE494A5F485E5BCA4BD88A2763AED1CA2B2FA8F0540678CD1E0F3AD80892",
+        n: "AADD9DB8DBE9C48B3FD4E6AE33C9FC07CB308DB3B3C9D20ED6639CCA70330870553E5C414CA92619418661197FAC10471DB1D381085DDADDB58796829CA90069",
+        h: 1,
+        glv: None,
+    },
+];
+
+static CACHE: [OnceLock<DomainParameters>; 8] = [const { OnceLock::new() }; 8];
+
+// Looks a curve up by its SEC 2 / RFC 5639 name or a NIST alias such as
+// "P-256". Names are matched case-insensitively.
+pub fn by_name(name: &str) -> Option<&'static DomainParameters> {
+    let index = CURVES.iter().position(|c| {
+        c.name.eq_ignore_ascii_case(name) || c.aliases.iter().any(|a| a.eq_ignore_ascii_case(name))
+    })?;
+    Some(load(index))
+}
+
+// Looks a curve up by its ASN.1 object identifier in dotted form.
+pub fn by_oid(oid: &str) -> Option<&'static DomainParameters> {
+    let index = CURVES.iter().position(|c| c.oid == oid)?;
+    Some(load(index))
+}
+
+// every supported curve name, in registry order
+pub fn names() -> impl Iterator<Item = &'static str> {
+    CURVES.iter().map(|c| c.name)
+}
+
+pub fn secp256k1() -> &'static DomainParameters {
+    load(0)
+}
+
+pub fn secp224r1() -> &'static DomainParameters {
+    load(1)
+}
+
+pub fn secp256r1() -> &'static DomainParameters {
+    load(2)
+}
+
+pub fn secp384r1() -> &'static DomainParameters {
+    load(3)
+}
+
+pub fn secp521r1() -> &'static DomainParameters {
+    load(4)
+}
+
+pub fn brainpool_p256r1() -> &'static DomainParameters {
+    load(5)
+}
+
+pub fn brainpool_p384r1() -> &'static DomainParameters {
+    load(6)
+}
+
+pub fn brainpool_p512r1() -> &'static DomainParameters {
+    load(7)
+}
+
+// Twisted Edwards curves a x^2 + y^2 = 1 + d x^2 y^2 from RFC 8032
+struct NamedEdwardsCurve {
+    name: &'static str,
+    p: &'static str,
+    a: &'static str,
+    d: &'static str,
+    gx: &'static str,
+    gy: &'static str,
+    n: &'static str,
+    h: u32,
+}
+
+const P25519: &str = "7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFED";
+const P448: &str = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF";
+
+static EDWARDS_CURVES: [NamedEdwardsCurve; 2] = [
+    NamedEdwardsCurve {
+        name: "edwards25519",
+        p: P25519,
+        a: "7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEC",
+        d: "52036CEE2B6FFE738CC740797779E89800700A4D4141D8AB75EB4DCA135978A3",
+        gx: "216936D3CD6E53FEC0A4E231FDD6DC5C692CC7609525A7B2C9562D608F25D51A",
+        gy: "6666666666666666666666666666666666666666666666666666666666666658",
+        n: "1000000000000000000000000000000014DEF9DEA2F79CD65812631A5CF5D3ED",
+        h: 8,
+    },
+    NamedEdwardsCurve {
+        name: "edwards448",
+        p: P448,
+        a: "01",
+        d: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF6756",
+        gx: "4F1970C66BED0DED221D15A622BF36DA9E146570470F1767EA6DE324A3D3A46412AE1AF72AB66511433B80E18B00938E2626A82BC70CC05E",
+        gy: "693F46716EB6BC248876203756C9C7624BEA73736CA3984087789C1E05A0C2D73AD3FF1CE67C39C4FDBD132C4ED7C8AD9808795BF230FA14",
+        n: "3FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF7CCA23E9C44EDB49AED63690216CC2728DC58F552378C292AB5844F3",
+        h: 4,
+    },
+];
+
+static EDWARDS_CACHE: [OnceLock<DomainParameters<TwistedEdwardsCurve>>; 2] =
+    [const { OnceLock::new() }; 2];
+
+pub fn edwards25519() -> &'static DomainParameters<TwistedEdwardsCurve> {
+    load_edwards(0)
+}
+
+pub fn edwards448() -> &'static DomainParameters<TwistedEdwardsCurve> {
+    load_edwards(1)
+}
+
+fn load_edwards(index: usize) -> &'static DomainParameters<TwistedEdwardsCurve> {
+    EDWARDS_CACHE[index].get_or_init(|| {
+        let c = &EDWARDS_CURVES[index];
+        DomainParameters::new(
+            TwistedEdwardsCurve::new(parse(c.a), parse(c.d), parse(c.p)),
+            Point::Coordinates(parse(c.gx), parse(c.gy)),
+            parse(c.n),
+            BigUint::from(c.h),
+        )
+        .unwrap_or_else(|e| panic!("built-in curve {} is invalid: {}", c.name, e))
+    })
+}
+
+static CURVE25519: OnceLock<MontgomeryCurve> = OnceLock::new();
+static CURVE448: OnceLock<MontgomeryCurve> = OnceLock::new();
+
+// Curve25519 (RFC 7748, 4.1): p = 2^255 - 19, A = 486662, B = 1
+pub fn curve25519() -> &'static MontgomeryCurve {
+    CURVE25519.get_or_init(|| {
+        let p = (BigUint::from(1u32) << 255u32) - 19u32;
+        load_montgomery("curve25519", 486662, p)
+    })
+}
+
+// Curve448 (RFC 7748, 4.2): p = 2^448 - 2^224 - 1, A = 156326, B = 1
+pub fn curve448() -> &'static MontgomeryCurve {
+    CURVE448.get_or_init(|| {
+        let p = (BigUint::from(1u32) << 448u32) - (BigUint::from(1u32) << 224u32) - 1u32;
+        load_montgomery("curve448", 156326, p)
+    })
+}
+
+fn load_montgomery(name: &str, a: u32, p: BigUint) -> MontgomeryCurve {
+    let curve = MontgomeryCurve::new(BigUint::from(a), BigUint::from(1u32), p);
+    curve
+        .validate()
+        .unwrap_or_else(|e| panic!("built-in curve {} is invalid: {}", name, e));
+    curve
+}
+
+fn load(index: usize) -> &'static DomainParameters {
+    CACHE[index].get_or_init(|| {
+        let c = &CURVES[index];
+        let params = DomainParameters::new(
+            EllipticCurve::new(parse(c.a), parse(c.b), parse(c.p)),
+            Point::Coordinates(parse(c.gx), parse(c.gy)),
+            parse(c.n),
+            BigUint::from(c.h),
+        );
+        let params = match &c.glv {
+            Some(glv) => params.and_then(|params| {
+                params.with_endomorphism(GlvEndomorphism::new(
+                    parse(glv.beta),
+                    parse(glv.lambda),
+                    (parse_signed(glv.a1), parse_signed(glv.b1)),
+                    (parse_signed(glv.a2), parse_signed(glv.b2)),
+                ))
+            }),
+            None => params,
+        };
+        params.unwrap_or_else(|e| panic!("built-in curve {} is invalid: {}", c.name, e))
+    })
+}
+
+fn parse(hex: &str) -> BigUint {
+    BigUint::parse_bytes(hex.as_bytes(), 16).expect("could not convert str to BigUint")
+}
+
+fn parse_signed(hex: &str) -> BigInt {
+    BigInt::parse_bytes(hex.as_bytes(), 16).expect("could not convert str to BigInt")
+}
+
+#[cfg(test)]
+mod curves_test {
+    use super::{by_name, by_oid, names, parse, secp256k1, CURVES};
+    use crate::elliptic_curve::Point;
+    use num_bigint::BigUint;
+
+    fn point(x: &str, y: &str) -> Point {
+        Point::Coordinates(parse(x), parse(y))
+    }
+
+    // k * G for a known (k, kG) pair
+    fn assert_known_answer(name: &str, k: &str, expected: Point) {
+        let params = by_name(name).expect("unknown curve");
+        let product = params.curve().scalar_mul(params.generator(), &parse(k));
+        assert_eq!(product, expected, "{}", name);
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(names().count(), CURVES.len());
+        for c in &CURVES {
+            let params = by_name(c.name).expect("unknown curve");
+            // the cache hands out the same instance every time
+            assert!(std::ptr::eq(params, by_oid(c.oid).expect("unknown oid")));
+            for alias in c.aliases {
+                assert!(std::ptr::eq(params, by_name(alias).expect("unknown alias")));
+            }
+        }
+        assert!(std::ptr::eq(by_name("SECP256K1").unwrap(), secp256k1()));
+        assert!(by_name("secp256k2").is_none());
+        assert!(by_oid("1.3.132.0.0").is_none());
+    }
+
+    #[test]
+    fn test_order_minus_one() {
+        // (n - 1) G = -G on every curve
+        for name in names() {
+            let params = by_name(name).expect("unknown curve");
+            let ec = params.curve();
+            let g = params.generator();
+            let product = ec.scalar_mul(g, &(params.order() - BigUint::from(1u32)));
+            assert_eq!(product, ec.negate(g), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_known_answers() {
+        assert_known_answer(
+            "secp256k1",
+            "02",
+            point(
+                "C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5",
+                "1AE168FEA63DC339A3C58419466CEAEEF7F632653266D0E1236431A950CFE52A",
+            ),
+        );
+        assert_known_answer(
+            "secp256k1",
+            "03",
+            point(
+                "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+                "388F7B0F632DE8140FE337E62A37F3566500A99934C2231B6CB9FD7584B8E672",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_nist_known_answers() {
+        assert_known_answer(
+            "P-224",
+            "02",
+            point(
+                "706A46DC76DCB76798E60E6D89474788D16DC18032D268FD1A704FA6",
+                "1C2B76A7BC25E7702A704FA986892849FCA629487ACF3709D2E4E8BB",
+            ),
+        );
+        assert_known_answer(
+            "P-256",
+            "02",
+            point(
+                "7CF27B188D034F7E8A52380304B51AC3C08969E277F21B35A60B48FC47669978",
+                "07775510DB8ED040293D9AC69F7430DBBA7DADE63CE982299E04B79D227873D1",
+            ),
+        );
+        assert_known_answer(
+            "P-384",
+            "02",
+            point(
+                "08D999057BA3D2D969260045C55B97F089025959A6F434D651D207D19FB96E9E4FE0E86EBE0E64F85B96A9C75295DF61",
+                "8E80F1FA5B1B3CEDB7BFE8DFFD6DBA74B275D875BC6CC43E904E505F256AB4255FFD43E94D39E22D61501E700A940E80",
+            ),
+        );
+        // RFC 6979, A.2.7: the P-521 key pair
+        assert_known_answer(
+            "P-521",
+            "0FAD06DAA62BA3B25D2FB40133DA757205DE67F5BB0018FEE8C86E1B68C7E75CAA896EB32F1F47C70855836A6D16FCC1466F6D8FBEC67DB89EC0C08B0E996B83538",
+            point(
+                "1894550D0785932E00EAA23B694F213F8C3121F86DC97A04E5A7167DB4E5BCD371123D46E45DB6B5D5370A7F20FB633155D38FFA16D2BD761DCAC474B9A2F5023A4",
+                "0493101C962CD4D2FDDF782285E64584139C2F91B47F87FF82354D6630F746A28A0DB25741B5B34A828008B22ACC23F924FAAFBD4D33F81EA66956DFEAA2BFDFCF5",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_brainpool_known_answers() {
+        // RFC 7027, A.1: Alice's key pair on brainpoolP256r1
+        assert_known_answer(
+            "brainpoolP256r1",
+            "81DB1EE100150FF2EA338D708271BE38300CB54241D79950F77B063039804F1D",
+            point(
+                "44106E913F92BC02A1705D9953A8414DB95E1AAA49E81D9E85F929A8E3100BE5",
+                "8AB4846F11CACCB73CE49CBDD120F5A900A69FD32C272223F789EF10EB089BDC",
+            ),
+        );
+        // RFC 7027, A.2: Alice's key pair on brainpoolP384r1
+        assert_known_answer(
+            "brainpoolP384r1",
+            "1E20F5E048A5886F1F157C74E91BDE2B98C8B52D58E5003D57053FC4B0BD65D6F15EB5D1EE1610DF870795143627D042",
+            point(
+                "68B665DD91C195800650CDD363C625F4E742E8134667B767B1B476793588F885AB698C852D4A6E77A252D6380FCAF068",
+                "55BC91A39C9EC01DEE36017B7D673A931236D2F1F5C83942D049E3FA20607493E0D038FF2FD30C2AB67D15C85F7FAA59",
+            ),
+        );
+        // RFC 7027, A.3: Alice's key pair on brainpoolP512r1
+        assert_known_answer(
+            "brainpoolP512r1",
+            "16302FF0DBBB5A8D733DAB7141C1B45ACBC8715939677F6A56850A38BD87BD59B09E80279609FF333EB9D4C061231FB26F92EEB04982A5F1D1764CAD57665422",
+            point(
+                "0A420517E406AAC0ACDCE90FCD71487718D3B953EFD7FBEC5F7F27E28C6149999397E91E029E06457DB2D3E640668B392C2A7E737A7F0BF04436D11640FD09FD",
+                "72E6882E8DB28AAD36237CD25D580DB23783961C8DC52DFA2EC138AD472A0FCEF3887CF62B623B2A87DE5C588301EA3E5FC269B373B60724F5E82A6AD147FDE7",
+            ),
+        );
+    }
+}
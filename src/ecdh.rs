@@ -0,0 +1,186 @@
+use crate::curves;
+use crate::elliptic_curve::MontgomeryCurve;
+use num_bigint::BigUint;
+
+// X25519 and X448 Diffie-Hellman as in RFC 7748, section 5, on byte strings.
+// A public key is x25519(k, &X25519_BASE_POINT) and the shared secret is
+// x25519(k, peer's public key); likewise for X448.
+
+// u = 9 on Curve25519
+pub const X25519_BASE_POINT: [u8; 32] = {
+    let mut u = [0u8; 32];
+    u[0] = 9;
+    u
+};
+
+// u = 5 on Curve448
+pub const X448_BASE_POINT: [u8; 56] = {
+    let mut u = [0u8; 56];
+    u[0] = 5;
+    u
+};
+
+pub fn x25519(k: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    // clamping: a multiple of the cofactor 8 with bit 254 set, so the
+    // ladder length does not depend on the key
+    let mut k = *k;
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+    // the unused top bit of the u-coordinate is ignored
+    let mut u = *u;
+    u[31] &= 127;
+    ladder(curves::curve25519(), &k, &u, 255)
+}
+
+pub fn x448(k: &[u8; 56], u: &[u8; 56]) -> [u8; 56] {
+    // clamping: a multiple of the cofactor 4 with bit 447 set
+    let mut k = *k;
+    k[0] &= 252;
+    k[55] |= 128;
+    ladder(curves::curve448(), &k, u, 448)
+}
+
+// Little-endian k and u in, u(kP) out in the same encoding. A
+// non-canonical u >= p is accepted and reduced, as the RFC requires.
+fn ladder<const N: usize>(curve: &MontgomeryCurve, k: &[u8; N], u: &[u8; N], bits: u64) -> [u8; N] {
+    let product = curve.ladder(&BigUint::from_bytes_le(k), &BigUint::from_bytes_le(u), bits);
+    let bytes = product.to_bytes_le();
+    let mut out = [0u8; N];
+    out[..bytes.len()].copy_from_slice(&bytes);
+    out
+}
+
+#[cfg(test)]
+mod ecdh_test {
+    use super::{x25519, x448, X25519_BASE_POINT, X448_BASE_POINT};
+
+    fn bytes<const N: usize>(hex: &str) -> [u8; N] {
+        hex::decode(hex)
+            .expect("could not convert str to bytes")
+            .try_into()
+            .expect("wrong length")
+    }
+
+    // k, u <- f(k, u), k, starting from k = u = base (RFC 7748, 5.2)
+    fn iterate<const N: usize>(
+        f: fn(&[u8; N], &[u8; N]) -> [u8; N],
+        base: [u8; N],
+        rounds: usize,
+    ) -> [u8; N] {
+        let (mut k, mut u) = (base, base);
+        for _ in 0..rounds {
+            (k, u) = (f(&k, &u), k);
+        }
+        k
+    }
+
+    #[test]
+    fn test_x25519_vectors() {
+        assert_eq!(
+            x25519(
+                &bytes("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"),
+                &bytes("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c"),
+            ),
+            bytes("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552")
+        );
+        // the top bit of u is set and ignored
+        assert_eq!(
+            x25519(
+                &bytes("4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d"),
+                &bytes("e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493"),
+            ),
+            bytes("95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957")
+        );
+    }
+
+    #[test]
+    fn test_x448_vectors() {
+        assert_eq!(
+            x448(
+                &bytes("3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3"),
+                &bytes("06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086"),
+            ),
+            bytes("ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaadeb445fc66a01b0779d98223961111e21766282f73dd96b6f")
+        );
+        assert_eq!(
+            x448(
+                &bytes("203d494428b8399352665ddca42f9de8fef600908e0d461cb021f8c538345dd77c3e4806e25f46d3315c44e0a5b4371282dd2c8d5be3095f"),
+                &bytes("0fbcc2f993cd56d3305b0b7d9e55d4c1a8fb5dbb52f8e9a1e9b6201b165d015894e56c4d3570bee52fe205e28a78b91cdfbde71ce8d157db"),
+            ),
+            bytes("884a02576239ff7a2f2f63b2db6a9ff37047ac13568e1e30fe63c4a7ad1b3ee3a5700df34321d62077e63633c575c1c954514e99da7c179d")
+        );
+    }
+
+    #[test]
+    fn test_iterated() {
+        assert_eq!(
+            iterate(x25519, X25519_BASE_POINT, 1),
+            bytes("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079")
+        );
+        assert_eq!(
+            iterate(x448, X448_BASE_POINT, 1),
+            bytes("3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd0db897086239492caf350b51f833868b9bc2b3bca9cf4113")
+        );
+        assert_eq!(
+            iterate(x25519, X25519_BASE_POINT, 1000),
+            bytes("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51")
+        );
+        assert_eq!(
+            iterate(x448, X448_BASE_POINT, 1000),
+            bytes("aa3b4749d55b9daf1e5b00288826c467274ce3ebbdd5c17b975e09d4af6c67cf10d087202db88286e2b79fceea3ec353ef54faa26e219f38")
+        );
+    }
+
+    // The million rounds take most of an hour in release mode:
+    // cargo test --release -- --ignored iterated
+    #[test]
+    #[ignore]
+    fn test_iterated_million() {
+        assert_eq!(
+            iterate(x25519, X25519_BASE_POINT, 1_000_000),
+            bytes("7c3911e0ab2586fd864497297e575e6f3bc601c0883c30df5f4dd2d24f665424")
+        );
+        assert_eq!(
+            iterate(x448, X448_BASE_POINT, 1_000_000),
+            bytes("077f453681caca3693198420bbe515cae0002472519b3e67661a7e89cab94695c8f4bcd66e61b9b9c946da8d524de3d69bd9d9d66b997e37")
+        );
+    }
+
+    #[test]
+    fn test_x25519_key_agreement() {
+        // RFC 7748, 6.1
+        let alice: [u8; 32] =
+            bytes("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let bob: [u8; 32] =
+            bytes("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+        let alice_public = x25519(&alice, &X25519_BASE_POINT);
+        let bob_public = x25519(&bob, &X25519_BASE_POINT);
+        assert_eq!(
+            alice_public,
+            bytes("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+        );
+        assert_eq!(
+            bob_public,
+            bytes("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+        );
+        let shared: [u8; 32] =
+            bytes("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(x25519(&alice, &bob_public), shared);
+        assert_eq!(x25519(&bob, &alice_public), shared);
+    }
+
+    #[test]
+    fn test_x448_key_agreement() {
+        // RFC 7748, 6.2
+        let alice: [u8; 56] = bytes("9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28dd9c9baf574a9419744897391006382a6f127ab1d9ac2d8c0a598726b");
+        let bob: [u8; 56] = bytes("1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d6927c120bb5ee8972b0d3e21374c9c921b09d1b0366f10b65173992d");
+        let alice_public = x448(&alice, &X448_BASE_POINT);
+        let bob_public = x448(&bob, &X448_BASE_POINT);
+        assert_eq!(alice_public, bytes("9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c22c5d9bbc836647241d953d40c5b12da88120d53177f80e532c41fa0"));
+        assert_eq!(bob_public, bytes("3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b43027d8b972fc3e34fb4232a13ca706dcb57aec3dae07bdc1c67bf33609"));
+        let shared: [u8; 56] = bytes("07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282bb60c0b56fd2464c335543936521c24403085d59a449a5037514a879d");
+        assert_eq!(x448(&alice, &bob_public), shared);
+        assert_eq!(x448(&bob, &alice_public), shared);
+    }
+}
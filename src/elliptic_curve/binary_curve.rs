@@ -0,0 +1,301 @@
+use super::binary_field::BinaryField;
+use super::curve_group::CurveGroup;
+use super::elliptic_curve::Point;
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+
+// Non-supersingular curve over GF(2^m). Coordinates are field polynomials
+// in the BigUint bit encoding used by BinaryField.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryCurve {
+    // y^2 + x*y = x^3 + a*x^2 + b
+    a: BigUint,
+    b: BigUint,
+    field: BinaryField,
+}
+
+impl BinaryCurve {
+    pub fn new(a: BigUint, b: BigUint, field: BinaryField) -> Self {
+        BinaryCurve { a, b, field }
+    }
+
+    pub fn a(&self) -> &BigUint {
+        &self.a
+    }
+
+    pub fn b(&self) -> &BigUint {
+        &self.b
+    }
+
+    pub fn field(&self) -> &BinaryField {
+        &self.field
+    }
+
+    // f(x) must be irreducible and b != 0, otherwise the curve is singular
+    pub fn validate(&self) -> Result<()> {
+        if !self.field.is_irreducible() {
+            return Err(EccError::ReducibleModulus);
+        }
+        if self.b == BigUint::from(0u32) {
+            return Err(EccError::SingularCurve);
+        }
+        Ok(())
+    }
+
+    pub fn add(&self, r: &Point, q: &Point) -> Point {
+        self.try_add(r, q).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // r == q falls through to doubling, r == -q gives the identity
+    pub fn try_add(&self, r: &Point, q: &Point) -> Result<Point> {
+        self.check_on_curve(r)?;
+        self.check_on_curve(q)?;
+
+        match (r, q) {
+            (Point::Identity, _) => Ok(q.clone()),
+            (_, Point::Identity) => Ok(r.clone()),
+            (Point::Coordinates(x1, y1), Point::Coordinates(x2, y2)) => {
+                if x1 == x2 {
+                    // same x: either q = -r = (x1, x1 + y1) or q = r
+                    if y1 != y2 {
+                        return Ok(Point::Identity);
+                    }
+                    return self.try_double(r);
+                }
+
+                let f = &self.field;
+                // lambda = (y1 + y2) / (x1 + x2)
+                let lambda = f.try_div(&f.add(y1, y2), &f.add(x1, x2))?;
+                // x3 = lambda^2 + lambda + x1 + x2 + a
+                let x3 = f.add(
+                    &f.add(&f.square(&lambda), &lambda),
+                    &f.add(&f.add(x1, x2), &self.a),
+                );
+                // y3 = lambda(x1 + x3) + x3 + y1
+                let y3 = f.add(&f.add(&f.mul(&lambda, &f.add(x1, &x3)), &x3), y1);
+                Ok(Point::Coordinates(x3, y3))
+            }
+        }
+    }
+
+    pub fn double(&self, c: &Point) -> Point {
+        self.try_double(c).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_double(&self, c: &Point) -> Result<Point> {
+        self.check_on_curve(c)?;
+
+        match c {
+            Point::Identity => Ok(Point::Identity),
+            Point::Coordinates(x, y) => {
+                // -(0, y) = (0, y), so the point has order 2
+                if x == &BigUint::from(0u32) {
+                    return Ok(Point::Identity);
+                }
+
+                let f = &self.field;
+                // lambda = x + y / x
+                let lambda = f.add(x, &f.try_div(y, x)?);
+                // x3 = lambda^2 + lambda + a
+                let x3 = f.add(&f.add(&f.square(&lambda), &lambda), &self.a);
+                // y3 = x^2 + (lambda + 1) x3
+                let y3 = f.add(
+                    &f.square(x),
+                    &f.mul(&f.add(&lambda, &BigUint::from(1u32)), &x3),
+                );
+                Ok(Point::Coordinates(x3, y3))
+            }
+        }
+    }
+
+    // -(x, y) = (x, x + y)
+    pub fn negate(&self, c: &Point) -> Point {
+        match c {
+            Point::Coordinates(x, y) => Point::Coordinates(x.clone(), self.field.add(x, y)),
+            Point::Identity => Point::Identity,
+        }
+    }
+
+    pub fn scalar_mul(&self, c: &Point, d: &BigUint) -> Point {
+        self.try_scalar_mul(c, d)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_scalar_mul(&self, c: &Point, d: &BigUint) -> Result<Point> {
+        CurveGroup::try_scalar_mul(self, c, d)
+    }
+
+    fn check_on_curve(&self, c: &Point) -> Result<()> {
+        if !self.is_on_curve(c) {
+            return Err(EccError::NotOnCurve(c.clone()));
+        }
+        Ok(())
+    }
+
+    pub fn is_on_curve(&self, c: &Point) -> bool {
+        match c {
+            Point::Coordinates(x, y) => {
+                let f = &self.field;
+                if !f.contains(x) || !f.contains(y) {
+                    return false;
+                }
+                // y^2 + x*y = x^3 + a*x^2 + b
+                let x2 = f.square(x);
+                let lhs = f.add(&f.square(y), &f.mul(x, y));
+                let rhs = f.add(&f.add(&f.mul(&x2, x), &f.mul(&self.a, &x2)), &self.b);
+                lhs == rhs
+            }
+            Point::Identity => true,
+        }
+    }
+}
+
+impl CurveGroup for BinaryCurve {
+    fn is_on_curve(&self, c: &Point) -> bool {
+        BinaryCurve::is_on_curve(self, c)
+    }
+
+    fn try_add(&self, r: &Point, q: &Point) -> Result<Point> {
+        BinaryCurve::try_add(self, r, q)
+    }
+
+    fn try_double(&self, c: &Point) -> Result<Point> {
+        BinaryCurve::try_double(self, c)
+    }
+
+    fn negate(&self, c: &Point) -> Point {
+        BinaryCurve::negate(self, c)
+    }
+
+    fn field_order(&self) -> BigUint {
+        self.field.order()
+    }
+
+    fn validate(&self) -> Result<()> {
+        BinaryCurve::validate(self)
+    }
+}
+
+#[cfg(test)]
+mod binary_curve_test {
+    use super::{BigUint, BinaryCurve, BinaryField, EccError, Point};
+
+    // sect233k1 (SEC 2, 3.3.1): f(x) = x^233 + x^74 + 1, a = 0, b = 1, h = 4
+    fn get_sect233k1() -> (BinaryCurve, Point, BigUint) {
+        let gx = BigUint::parse_bytes(
+            b"017232BA853A7E731AF129F22FF4149563A419C26BF50A4C9D6EEFAD6126",
+            16,
+        )
+        .expect("could not convert str to gx");
+        let gy = BigUint::parse_bytes(
+            b"01DB537DECE819B7F70F555A67C427A8CD9BF18AEB9B56E0C11056FAE6A3",
+            16,
+        )
+        .expect("could not convert str to gy");
+        let n = BigUint::parse_bytes(
+            b"8000000000000000000000000000069D5BB915BCD46EFB1AD5F173ABDF",
+            16,
+        )
+        .expect("could not convert str to n");
+
+        let ec = BinaryCurve::new(
+            BigUint::from(0u32),
+            BigUint::from(1u32),
+            BinaryField::trinomial(233, 74).expect("could not build the field"),
+        );
+        (ec, Point::Coordinates(gx, gy), n)
+    }
+
+    // sect283r1 (SEC 2, 3.4.2): f(x) = x^283 + x^12 + x^7 + x^5 + 1, a = 1, h = 2
+    fn get_sect283r1() -> (BinaryCurve, Point, BigUint) {
+        let b = BigUint::parse_bytes(
+            b"027B680AC8B8596DA5A4AF8A19A0303FCA97FD7645309FA2A581485AF6263E313B79A2F5",
+            16,
+        )
+        .expect("could not convert str to b");
+        let gx = BigUint::parse_bytes(
+            b"05F939258DB7DD90E1934F8C70B0DFEC2EED25B8557EAC9C80E2E198F8CDBECD86B12053",
+            16,
+        )
+        .expect("could not convert str to gx");
+        let gy = BigUint::parse_bytes(
+            b"03676854FE24141CB98FE6D4B20D02B4516FF702350EDDB0826779C813F0DF45BE8112F4",
+            16,
+        )
+        .expect("could not convert str to gy");
+        let n = BigUint::parse_bytes(
+            b"03FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEF90399660FC938A90165B042A7CEFADB307",
+            16,
+        )
+        .expect("could not convert str to n");
+
+        let ec = BinaryCurve::new(
+            BigUint::from(1u32),
+            b,
+            BinaryField::pentanomial(283, 12, 7, 5).expect("could not build the field"),
+        );
+        (ec, Point::Coordinates(gx, gy), n)
+    }
+
+    #[test]
+    fn test_small_curve() {
+        // y^2 + xy = x^3 + x^2 + 1 over GF(2^4), f(x) = x^4 + x + 1
+        let ec = BinaryCurve::new(
+            BigUint::from(1u32),
+            BigUint::from(1u32),
+            BinaryField::trinomial(4, 1).expect("could not build the field"),
+        );
+        let mut points = vec![Point::Identity];
+        for x in 0..16u32 {
+            for y in 0..16u32 {
+                let c = Point::Coordinates(BigUint::from(x), BigUint::from(y));
+                if ec.is_on_curve(&c) {
+                    points.push(c);
+                }
+            }
+        }
+        let order = BigUint::from(points.len());
+
+        for c in &points {
+            assert_eq!(ec.add(c, &ec.negate(c)), Point::Identity);
+            assert_eq!(ec.add(c, c), ec.double(c));
+            assert_eq!(ec.scalar_mul(c, &order), Point::Identity);
+            for q in &points {
+                let sum = ec.add(c, q);
+                assert!(ec.is_on_curve(&sum));
+                assert_eq!(sum, ec.add(q, c));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sect233k1() {
+        let (ec, g, n) = get_sect233k1();
+        assert!(ec.is_on_curve(&g));
+        assert_eq!(ec.scalar_mul(&g, &n), Point::Identity);
+
+        let g2 = ec.double(&g);
+        let g3 = ec.add(&g2, &g);
+        assert_eq!(ec.scalar_mul(&g, &BigUint::from(3u32)), g3);
+        assert_eq!(ec.scalar_mul(&g, &(&n - 1u32)), ec.negate(&g));
+    }
+
+    #[test]
+    fn test_sect283r1() {
+        let (ec, g, n) = get_sect283r1();
+        assert!(ec.is_on_curve(&g));
+        assert_eq!(ec.scalar_mul(&g, &n), Point::Identity);
+        assert_eq!(ec.scalar_mul(&g, &(&n + 1u32)), g);
+    }
+
+    #[test]
+    fn test_not_on_curve() {
+        let (ec, _, _) = get_sect233k1();
+        let c = Point::Coordinates(BigUint::from(2u32), BigUint::from(1u32));
+        assert_eq!(ec.try_double(&c), Err(EccError::NotOnCurve(c.clone())));
+        assert_eq!(
+            ec.try_add(&Point::Identity, &c),
+            Err(EccError::NotOnCurve(c))
+        );
+    }
+}
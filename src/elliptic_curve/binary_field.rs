@@ -0,0 +1,389 @@
+use super::field::Field;
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+
+// GF(2^m) in polynomial basis. An element is a polynomial over GF(2) of
+// degree < m, stored as the BigUint whose bit i is the coefficient of x^i.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryField {
+    m: u64,
+    // exponents k of the reduction polynomial f(x) = x^m + sum x^k, highest first
+    terms: Vec<u64>,
+    // f(x) itself
+    modulus: BigUint,
+    // always 2
+    characteristic: BigUint,
+}
+
+impl BinaryField {
+    // f(x) = x^m + x^k + 1, with 0 < k < m
+    pub fn trinomial(m: u64, k: u64) -> Result<Self> {
+        Self::new(m, vec![k])
+    }
+
+    // f(x) = x^m + x^k1 + x^k2 + x^k3 + 1, with distinct 0 < ki < m
+    pub fn pentanomial(m: u64, k1: u64, k2: u64, k3: u64) -> Result<Self> {
+        Self::new(m, vec![k1, k2, k3])
+    }
+
+    // A term at or above x^m would make reduce() grow the degree instead
+    // of lowering it, and a repeated term would cancel in f(x) but not in
+    // reduce(), so both are rejected here.
+    fn new(m: u64, mut terms: Vec<u64>) -> Result<Self> {
+        for (i, &k) in terms.iter().enumerate() {
+            if k == 0 || k >= m || terms[..i].contains(&k) {
+                return Err(EccError::InvalidReductionTerm { term: k, degree: m });
+            }
+        }
+        // the constant term 1
+        terms.push(0);
+        terms.sort_unstable_by(|a, b| b.cmp(a));
+        let mut modulus = BigUint::from(1u32) << m;
+        for &k in &terms {
+            modulus.set_bit(k, true);
+        }
+        Ok(BinaryField {
+            m,
+            terms,
+            modulus,
+            characteristic: BigUint::from(2u32),
+        })
+    }
+
+    pub fn m(&self) -> u64 {
+        self.m
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    // 2^m
+    pub fn order(&self) -> BigUint {
+        BigUint::from(1u32) << self.m
+    }
+
+    // true if a is a reduced polynomial, i.e. has degree < m
+    pub fn contains(&self, a: &BigUint) -> bool {
+        a.bits() <= self.m
+    }
+
+    pub fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a ^ b
+    }
+
+    pub fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        self.reduce(&carryless_mul(a, b))
+    }
+
+    pub fn square(&self, a: &BigUint) -> BigUint {
+        self.mul(a, a)
+    }
+
+    pub fn div(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        self.try_div(a, b).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_div(&self, a: &BigUint, b: &BigUint) -> Result<BigUint> {
+        Ok(self.mul(a, &self.try_inv_mul(b)?))
+    }
+
+    pub fn inv_mul(&self, a: &BigUint) -> BigUint {
+        self.try_inv_mul(a).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // Extended Euclid over GF(2)[x] (Guide to ECC, Algorithm 2.48).
+    pub fn try_inv_mul(&self, a: &BigUint) -> Result<BigUint> {
+        let mut u = self.reduce(a);
+        if u == BigUint::from(0u32) {
+            return Err(EccError::NonInvertible {
+                value: a.clone(),
+                modulus: self.modulus.clone(),
+            });
+        }
+
+        let one = BigUint::from(1u32);
+        let mut v = self.modulus.clone();
+        let (mut g1, mut g2) = (one.clone(), BigUint::from(0u32));
+        // invariant: g1 * a = u and g2 * a = v (mod f)
+        while u != one {
+            if u.bits() < v.bits() {
+                std::mem::swap(&mut u, &mut v);
+                std::mem::swap(&mut g1, &mut g2);
+            }
+            let j = u.bits() - v.bits();
+            u ^= &v << j;
+            g1 ^= &g2 << j;
+            // u = 0 leaves gcd(a, f) = v != 1, which a reducible f allows
+            if u == BigUint::from(0u32) {
+                return Err(EccError::NonInvertible {
+                    value: a.clone(),
+                    modulus: self.modulus.clone(),
+                });
+            }
+        }
+        Ok(self.reduce(&g1))
+    }
+
+    // Rabin's test: f is irreducible iff x^(2^m) = x (mod f) and
+    // gcd(x^(2^(m/r)) - x, f) = 1 for every prime r dividing m.
+    pub fn is_irreducible(&self) -> bool {
+        let x = self.reduce(&BigUint::from(2u32));
+        let one = BigUint::from(1u32);
+        for r in prime_factors(self.m) {
+            let h = self.add(&Field::frobenius_map(self, &x, (self.m / r) as usize), &x);
+            if poly_gcd(&h, &self.modulus) != one {
+                return false;
+            }
+        }
+        Field::frobenius_map(self, &x, self.m as usize) == x
+    }
+
+    // Folds the part above x^m back using x^m = sum x^k (mod f).
+    pub fn reduce(&self, a: &BigUint) -> BigUint {
+        let mut a = a.clone();
+        while a.bits() > self.m {
+            let high = &a >> self.m;
+            let mut low = a;
+            for i in self.m..low.bits() {
+                low.set_bit(i, false);
+            }
+            for &k in &self.terms {
+                low ^= &high << k;
+            }
+            a = low;
+        }
+        a
+    }
+}
+
+// Product of two GF(2) polynomials without reduction. The 64 bit-shifted
+// copies of a are built once, then each set bit of b xors one of them in
+// at the right limb offset.
+fn carryless_mul(a: &BigUint, b: &BigUint) -> BigUint {
+    let a_limbs = a.to_u64_digits();
+    let b_limbs = b.to_u64_digits();
+    if a_limbs.is_empty() || b_limbs.is_empty() {
+        return BigUint::from(0u32);
+    }
+
+    let shifted: Vec<Vec<u64>> = (0..64)
+        .map(|s| {
+            let mut out = vec![0u64; a_limbs.len() + 1];
+            for (i, &limb) in a_limbs.iter().enumerate() {
+                out[i] |= limb << s;
+                if s > 0 {
+                    out[i + 1] |= limb >> (64 - s);
+                }
+            }
+            out
+        })
+        .collect();
+
+    let mut product = vec![0u64; a_limbs.len() + b_limbs.len() + 1];
+    for (j, &limb) in b_limbs.iter().enumerate() {
+        for (s, row) in shifted.iter().enumerate() {
+            if (limb >> s) & 1 == 1 {
+                for (i, &word) in row.iter().enumerate() {
+                    product[i + j] ^= word;
+                }
+            }
+        }
+    }
+
+    let bytes: Vec<u8> = product.iter().flat_map(|w| w.to_le_bytes()).collect();
+    BigUint::from_bytes_le(&bytes)
+}
+
+// a mod b for GF(2) polynomials, b != 0
+fn poly_rem(a: &BigUint, b: &BigUint) -> BigUint {
+    let mut a = a.clone();
+    while a.bits() >= b.bits() {
+        a ^= b << (a.bits() - b.bits());
+    }
+    a
+}
+
+fn poly_gcd(a: &BigUint, b: &BigUint) -> BigUint {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    while b != BigUint::from(0u32) {
+        let r = poly_rem(&a, &b);
+        a = std::mem::replace(&mut b, r);
+    }
+    a
+}
+
+fn prime_factors(mut n: u64) -> Vec<u64> {
+    let mut factors = Vec::new();
+    let mut d = 2;
+    while d * d <= n {
+        if n.is_multiple_of(d) {
+            factors.push(d);
+            while n.is_multiple_of(d) {
+                n /= d;
+            }
+        }
+        d += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
+impl Field for BinaryField {
+    type Element = BigUint;
+
+    fn characteristic(&self) -> &BigUint {
+        &self.characteristic
+    }
+
+    fn zero(&self) -> BigUint {
+        BigUint::from(0u32)
+    }
+
+    fn one(&self) -> BigUint {
+        BigUint::from(1u32)
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        BinaryField::add(self, a, b)
+    }
+
+    // subtraction is addition in characteristic 2
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        BinaryField::add(self, a, b)
+    }
+
+    fn neg(&self, a: &BigUint) -> BigUint {
+        a.clone()
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        BinaryField::mul(self, a, b)
+    }
+
+    fn inv(&self, a: &BigUint) -> Result<BigUint> {
+        self.try_inv_mul(a)
+    }
+
+    // a -> a^2
+    fn frobenius(&self, a: &BigUint) -> BigUint {
+        BinaryField::square(self, a)
+    }
+}
+
+#[cfg(test)]
+mod binary_field_test {
+    use super::{BigUint, BinaryField, EccError, Field, Result};
+
+    // GF(2^4) with f(x) = x^4 + x + 1
+    fn gf16() -> BinaryField {
+        BinaryField::trinomial(4, 1).expect("could not build the field")
+    }
+
+    #[test]
+    fn test_add() {
+        let f = gf16();
+        // (x^3 + x) + (x^3 + 1) = x + 1
+        assert_eq!(
+            f.add(&BigUint::from(0b1010u32), &BigUint::from(0b1001u32)),
+            BigUint::from(0b0011u32)
+        );
+    }
+
+    #[test]
+    fn test_mul() {
+        let f = gf16();
+        // (x^3 + x^2 + 1)(x^2 + 1) = x^5 + x^4 + x^3 + 1 = x^3 + x^2 mod f
+        assert_eq!(
+            f.mul(&BigUint::from(0b1101u32), &BigUint::from(0b0101u32)),
+            BigUint::from(0b1100u32)
+        );
+        // x is a generator of GF(16)*, so x^15 = 1
+        let x = BigUint::from(2u32);
+        assert_eq!(
+            Field::pow(&f, &x, &BigUint::from(15u32)),
+            BigUint::from(1u32)
+        );
+        assert_ne!(
+            Field::pow(&f, &x, &BigUint::from(5u32)),
+            BigUint::from(1u32)
+        );
+    }
+
+    #[test]
+    fn test_inv() {
+        let f = gf16();
+        for a in 1..16u32 {
+            let a = BigUint::from(a);
+            assert_eq!(f.mul(&a, &f.inv_mul(&a)), BigUint::from(1u32));
+        }
+        assert_eq!(
+            f.try_inv_mul(&BigUint::from(0u32)),
+            Err(EccError::NonInvertible {
+                value: BigUint::from(0u32),
+                modulus: BigUint::from(0b10011u32)
+            })
+        );
+
+        // x^4 + x^2 + 1 = (x^2 + x + 1)^2, so x^2 + x + 1 has no inverse
+        let f = BinaryField::trinomial(4, 2).expect("could not build the field");
+        assert_eq!(
+            f.try_inv_mul(&BigUint::from(7u32)),
+            Err(EccError::NonInvertible {
+                value: BigUint::from(7u32),
+                modulus: BigUint::from(0b10101u32)
+            })
+        );
+    }
+
+    #[test]
+    fn test_is_irreducible() {
+        let irreducible =
+            |f: Result<BinaryField>| f.expect("could not build the field").is_irreducible();
+        assert!(gf16().is_irreducible());
+        assert!(irreducible(BinaryField::trinomial(233, 74)));
+        assert!(irreducible(BinaryField::pentanomial(283, 12, 7, 5)));
+        // x^4 + x^2 + 1 = (x^2 + x + 1)^2
+        assert!(!irreducible(BinaryField::trinomial(4, 2)));
+        // x^6 + x^3 + 1 is irreducible, x^6 + x^2 + 1 = (x^3 + x + 1)^2 is not
+        assert!(irreducible(BinaryField::trinomial(6, 3)));
+        assert!(!irreducible(BinaryField::trinomial(6, 2)));
+    }
+
+    #[test]
+    fn test_invalid_terms() {
+        let err = |term| Err(EccError::InvalidReductionTerm { term, degree: 4 });
+        assert_eq!(BinaryField::trinomial(4, 4), err(4));
+        assert_eq!(BinaryField::trinomial(4, 9), err(9));
+        assert_eq!(BinaryField::trinomial(4, 0), err(0));
+        assert_eq!(BinaryField::pentanomial(4, 3, 1, 3), err(3));
+    }
+
+    #[test]
+    fn test_large_field() {
+        // sect283 field, f(x) = x^283 + x^12 + x^7 + x^5 + 1
+        let f = BinaryField::pentanomial(283, 12, 7, 5).expect("could not build the field");
+        let a = BigUint::parse_bytes(
+            b"05F939258DB7DD90E1934F8C70B0DFEC2EED25B8557EAC9C80E2E198F8CDBECD86B12053",
+            16,
+        )
+        .expect("could not convert str to a");
+        let b = BigUint::parse_bytes(
+            b"03676854FE24141CB98FE6D4B20D02B4516FF702350EDDB0826779C813F0DF45BE8112F4",
+            16,
+        )
+        .expect("could not convert str to b");
+
+        let product = f.mul(&a, &b);
+        assert!(f.contains(&product));
+        assert_eq!(f.mul(&product, &f.inv_mul(&b)), a);
+        assert_eq!(
+            f.mul(&a, &f.add(&b, &BigUint::from(1u32))),
+            f.add(&product, &a)
+        );
+        // Frobenius of order m: a^(2^m) = a
+        assert_eq!(f.frobenius_map(&a, 283), a);
+    }
+}
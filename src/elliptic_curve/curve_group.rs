@@ -0,0 +1,223 @@
+use super::elliptic_curve::{EllipticCurve, Point};
+use super::fixed_base::{check_window, FixedBaseTable};
+use super::wnaf::{check_wnaf_width, wnaf};
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+
+// Group law of a curve in affine coordinates. Scalar multiplication and
+// ECDSA are written against this trait so prime, binary and twisted Edwards
+// curves share them.
+pub trait CurveGroup {
+    fn is_on_curve(&self, c: &Point) -> bool;
+
+    // r + q, including r == q and r == -q
+    fn try_add(&self, r: &Point, q: &Point) -> Result<Point>;
+
+    fn try_double(&self, c: &Point) -> Result<Point>;
+
+    // -c
+    fn negate(&self, c: &Point) -> Point;
+
+    // q, the number of elements of the base field
+    fn field_order(&self) -> BigUint;
+
+    // checks the base field and that the curve is non-singular
+    fn validate(&self) -> Result<()>;
+
+    fn try_scalar_mul(&self, c: &Point, d: &BigUint) -> Result<Point> {
+        if !self.is_on_curve(c) {
+            return Err(EccError::NotOnCurve(c.clone()));
+        }
+        if d == &BigUint::from(0u32) {
+            return Ok(Point::Identity);
+        }
+
+        let mut t = c.clone();
+        for i in (0..(d.bits() - 1)).rev() {
+            t = self.try_double(&t)?;
+            if d.bit(i) {
+                t = self.try_add(&t, c)?;
+            }
+        }
+        Ok(t)
+    }
+
+    // wNAF with window w, for public scalars only. The affine fallback
+    // costs an inversion per step; EllipticCurve overrides it.
+    fn try_scalar_mul_vartime(&self, c: &Point, d: &BigUint, w: usize) -> Result<Point> {
+        check_wnaf_width(w)?;
+        if !self.is_on_curve(c) {
+            return Err(EccError::NotOnCurve(c.clone()));
+        }
+
+        let double = self.try_double(c)?;
+        let mut table = vec![c.clone()];
+        for i in 1..1usize << (w - 2) {
+            let next = self.try_add(&table[i - 1], &double)?;
+            table.push(next);
+        }
+
+        let mut t = Point::Identity;
+        for &digit in wnaf(d, w).iter().rev() {
+            t = self.try_double(&t)?;
+            if digit != 0 {
+                let q = &table[(digit.unsigned_abs() as usize - 1) / 2];
+                t = if digit > 0 {
+                    self.try_add(&t, q)?
+                } else {
+                    self.try_add(&t, &self.negate(q))?
+                };
+            }
+        }
+        Ok(t)
+    }
+
+    // k1 P1 + k2 P2 + ... for public scalars, with wNAF window w. The
+    // fallback sums separate products; EllipticCurve interleaves them.
+    fn try_multi_scalar_mul(&self, terms: &[(Point, BigUint)], w: usize) -> Result<Point> {
+        let mut acc = Point::Identity;
+        for (c, k) in terms {
+            acc = self.try_add(&acc, &self.try_scalar_mul_vartime(c, k, w)?)?;
+        }
+        Ok(acc)
+    }
+
+    // Table for k * g with k of up to `bits` bits. The fallback stores no
+    // rows: try_fixed_base_mul below never reads them, and building them
+    // would cost one affine addition (and inversion) per entry.
+    fn fixed_base_table(&self, g: &Point, bits: u64, w: usize) -> Result<FixedBaseTable> {
+        check_window(w)?;
+        if !self.is_on_curve(g) {
+            return Err(EccError::NotOnCurve(g.clone()));
+        }
+        Ok(FixedBaseTable::new(g.clone(), w, bits, Vec::new()))
+    }
+
+    // k * g for a secret k. Indexing rows by the digits of k would leak them
+    // through the access pattern and the branching affine addition, so the
+    // fallback runs the ladder on the table's base over max_bits() bits
+    // instead. Only EllipticCurve overrides it, with a masked comb.
+    fn try_fixed_base_mul(&self, table: &FixedBaseTable, k: &BigUint) -> Result<Point> {
+        table.check_scalar(k)?;
+        self.try_ladder_scalar_mul(table.base(), k, table.max_bits())
+    }
+
+    // Montgomery ladder over exactly `bits` bits of d: one add and one
+    // double per bit whatever the bit values, for secret scalars. The
+    // affine group law underneath still branches on the identity, so curves
+    // with complete formulas should override this.
+    fn try_ladder_scalar_mul(&self, c: &Point, d: &BigUint, bits: u64) -> Result<Point> {
+        if !self.is_on_curve(c) {
+            return Err(EccError::NotOnCurve(c.clone()));
+        }
+        check_ladder_bits(d, bits)?;
+
+        let (mut r0, mut r1) = (Point::Identity, c.clone());
+        for i in (0..bits).rev() {
+            if d.bit(i) {
+                std::mem::swap(&mut r0, &mut r1);
+            }
+            r1 = self.try_add(&r0, &r1)?;
+            r0 = self.try_double(&r0)?;
+            if d.bit(i) {
+                std::mem::swap(&mut r0, &mut r1);
+            }
+        }
+        Ok(r0)
+    }
+
+    // true if c is on the curve and n c = O, i.e. c lies in the subgroup of
+    // order n (for prime n, c is the identity or has order exactly n)
+    fn is_in_subgroup(&self, c: &Point, n: &BigUint) -> bool {
+        self.is_on_curve(c) && matches!(self.try_scalar_mul(c, n), Ok(Point::Identity))
+    }
+
+    // h c, which lands in the subgroup of order n when #E = h n and
+    // gcd(h, n) = 1
+    fn try_clear_cofactor(&self, c: &Point, h: &BigUint) -> Result<Point> {
+        self.try_scalar_mul(c, h)
+    }
+
+    // Order of c given the factorization [(p, e), ...] of a multiple N of
+    // it, usually the group order h n. For each p, divide p out of N while
+    // (N / p) c is still the identity.
+    fn try_point_order(&self, c: &Point, factorization: &[(BigUint, u32)]) -> Result<BigUint> {
+        let mut order = factorization
+            .iter()
+            .fold(BigUint::from(1u32), |acc, (p, e)| acc * p.pow(*e));
+        if self.try_scalar_mul(c, &order)? != Point::Identity {
+            return Err(EccError::InvalidPointOrder);
+        }
+        for (p, e) in factorization {
+            for _ in 0..*e {
+                let candidate = &order / p;
+                if self.try_scalar_mul(c, &candidate)? != Point::Identity {
+                    break;
+                }
+                order = candidate;
+            }
+        }
+        Ok(order)
+    }
+}
+
+// d must fit in the fixed ladder length
+pub(crate) fn check_ladder_bits(d: &BigUint, bits: u64) -> Result<()> {
+    if d.bits() > bits {
+        return Err(EccError::ScalarOutOfRange {
+            value: d.clone(),
+            modulus: BigUint::from(1u32) << bits,
+        });
+    }
+    Ok(())
+}
+
+impl CurveGroup for EllipticCurve {
+    fn is_on_curve(&self, c: &Point) -> bool {
+        EllipticCurve::is_on_curve(self, c)
+    }
+
+    fn try_add(&self, r: &Point, q: &Point) -> Result<Point> {
+        EllipticCurve::try_add(self, r, q)
+    }
+
+    fn try_double(&self, c: &Point) -> Result<Point> {
+        EllipticCurve::try_double(self, c)
+    }
+
+    fn negate(&self, c: &Point) -> Point {
+        EllipticCurve::negate(self, c)
+    }
+
+    fn field_order(&self) -> BigUint {
+        self.p().clone()
+    }
+
+    fn validate(&self) -> Result<()> {
+        EllipticCurve::validate(self)
+    }
+
+    fn try_scalar_mul(&self, c: &Point, d: &BigUint) -> Result<Point> {
+        EllipticCurve::try_scalar_mul(self, c, d)
+    }
+
+    fn try_scalar_mul_vartime(&self, c: &Point, d: &BigUint, w: usize) -> Result<Point> {
+        EllipticCurve::try_scalar_mul_vartime(self, c, d, w)
+    }
+
+    fn try_ladder_scalar_mul(&self, c: &Point, d: &BigUint, bits: u64) -> Result<Point> {
+        EllipticCurve::try_ladder_scalar_mul(self, c, d, bits)
+    }
+
+    fn try_multi_scalar_mul(&self, terms: &[(Point, BigUint)], w: usize) -> Result<Point> {
+        EllipticCurve::try_multi_scalar_mul_windowed(self, terms, w)
+    }
+
+    fn fixed_base_table(&self, g: &Point, bits: u64, w: usize) -> Result<FixedBaseTable> {
+        EllipticCurve::fixed_base_table(self, g, bits, w)
+    }
+
+    fn try_fixed_base_mul(&self, table: &FixedBaseTable, k: &BigUint) -> Result<Point> {
+        EllipticCurve::try_fixed_base_mul(self, table, k)
+    }
+}
@@ -0,0 +1,350 @@
+use super::curve_group::CurveGroup;
+use super::elliptic_curve::{EllipticCurve, Point};
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+use std::hash::{Hash, Hasher};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+// A point bundled with the curve it lies on, so the group law can be written
+// with operators: -p, p + q, p - q, k * p and iterator sums. The identity
+// does not need a curve, which lets sums start from CurvePoint::identity().
+// Operators panic when the operands are on different curves, use try_* to
+// handle it.
+#[derive(Debug, Clone)]
+pub struct CurvePoint<'a, C: CurveGroup = EllipticCurve> {
+    // None only for an identity that has not met a curve yet
+    curve: Option<&'a C>,
+    point: Point,
+}
+
+impl<'a, C: CurveGroup + PartialEq> CurvePoint<'a, C> {
+    pub fn new(curve: &'a C, point: Point) -> Result<Self> {
+        if !curve.is_on_curve(&point) {
+            return Err(EccError::NotOnCurve(point));
+        }
+        Ok(CurvePoint {
+            curve: Some(curve),
+            point,
+        })
+    }
+
+    pub fn identity() -> Self {
+        CurvePoint {
+            curve: None,
+            point: Point::Identity,
+        }
+    }
+
+    pub fn point(&self) -> &Point {
+        &self.point
+    }
+
+    pub fn into_point(self) -> Point {
+        self.point
+    }
+
+    pub fn curve(&self) -> Option<&'a C> {
+        self.curve
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.point == Point::Identity
+    }
+
+    pub fn try_add(&self, other: &Self) -> Result<Self> {
+        let curve = match self.common_curve(other)? {
+            Some(curve) => curve,
+            None => return Ok(Self::identity()),
+        };
+        Ok(CurvePoint {
+            curve: Some(curve),
+            point: curve.try_add(&self.point, &other.point)?,
+        })
+    }
+
+    pub fn try_sub(&self, other: &Self) -> Result<Self> {
+        self.try_add(&-other)
+    }
+
+    pub fn double(&self) -> Self {
+        match self.curve {
+            Some(curve) => CurvePoint {
+                curve: Some(curve),
+                point: curve
+                    .try_double(&self.point)
+                    .unwrap_or_else(|e| panic!("{}", e)),
+            },
+            None => Self::identity(),
+        }
+    }
+
+    // Variable time in k, like the curve's scalar_mul; secret scalars belong
+    // in the ladder or a fixed-base table.
+    pub fn scalar_mul(&self, k: &BigUint) -> Self {
+        match self.curve {
+            Some(curve) => CurvePoint {
+                curve: Some(curve),
+                point: curve
+                    .try_scalar_mul(&self.point, k)
+                    .unwrap_or_else(|e| panic!("{}", e)),
+            },
+            None => Self::identity(),
+        }
+    }
+
+    // the curve both operands live on, None if both are unbound identities
+    fn common_curve(&self, other: &Self) -> Result<Option<&'a C>> {
+        match (self.curve, other.curve) {
+            (Some(a), Some(b)) if !std::ptr::eq(a, b) && a != b => Err(EccError::CurveMismatch),
+            (Some(a), _) => Ok(Some(a)),
+            (None, b) => Ok(b),
+        }
+    }
+}
+
+impl<C: CurveGroup + PartialEq> Default for CurvePoint<'_, C> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+// Equal points on the same curve; the identity equals the identity of any
+// curve.
+impl<C: CurveGroup + PartialEq> PartialEq for CurvePoint<'_, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point && (self.is_identity() || self.common_curve(other).is_ok())
+    }
+}
+
+impl<C: CurveGroup + PartialEq> Eq for CurvePoint<'_, C> {}
+
+// hashes the coordinates only, which is consistent with eq
+impl<C: CurveGroup> Hash for CurvePoint<'_, C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.point.hash(state);
+    }
+}
+
+impl<'a, C: CurveGroup + PartialEq> Neg for &CurvePoint<'a, C> {
+    type Output = CurvePoint<'a, C>;
+
+    fn neg(self) -> CurvePoint<'a, C> {
+        match self.curve {
+            Some(curve) => CurvePoint {
+                curve: Some(curve),
+                point: curve.negate(&self.point),
+            },
+            None => CurvePoint::identity(),
+        }
+    }
+}
+
+impl<'a, C: CurveGroup + PartialEq> Neg for CurvePoint<'a, C> {
+    type Output = CurvePoint<'a, C>;
+
+    fn neg(self) -> CurvePoint<'a, C> {
+        -&self
+    }
+}
+
+// Same shape as the FieldElement operators: every owned/borrowed combination
+// plus the *Assign trait.
+macro_rules! impl_binary_op {
+    ($op:ident, $method:ident, $try_method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl<'a, C: CurveGroup + PartialEq> $op<&CurvePoint<'a, C>> for &CurvePoint<'a, C> {
+            type Output = CurvePoint<'a, C>;
+
+            fn $method(self, rhs: &CurvePoint<'a, C>) -> CurvePoint<'a, C> {
+                self.$try_method(rhs).unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+
+        impl<'a, C: CurveGroup + PartialEq> $op<CurvePoint<'a, C>> for &CurvePoint<'a, C> {
+            type Output = CurvePoint<'a, C>;
+
+            fn $method(self, rhs: CurvePoint<'a, C>) -> CurvePoint<'a, C> {
+                self.$method(&rhs)
+            }
+        }
+
+        impl<'a, C: CurveGroup + PartialEq> $op<&CurvePoint<'a, C>> for CurvePoint<'a, C> {
+            type Output = CurvePoint<'a, C>;
+
+            fn $method(self, rhs: &CurvePoint<'a, C>) -> CurvePoint<'a, C> {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl<'a, C: CurveGroup + PartialEq> $op<CurvePoint<'a, C>> for CurvePoint<'a, C> {
+            type Output = CurvePoint<'a, C>;
+
+            fn $method(self, rhs: CurvePoint<'a, C>) -> CurvePoint<'a, C> {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl<'a, C: CurveGroup + PartialEq> $op_assign<&CurvePoint<'a, C>> for CurvePoint<'a, C> {
+            fn $method_assign(&mut self, rhs: &CurvePoint<'a, C>) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+
+        impl<'a, C: CurveGroup + PartialEq> $op_assign<CurvePoint<'a, C>> for CurvePoint<'a, C> {
+            fn $method_assign(&mut self, rhs: CurvePoint<'a, C>) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, try_add, AddAssign, add_assign);
+impl_binary_op!(Sub, sub, try_sub, SubAssign, sub_assign);
+
+// k * p, with the scalar on either side
+impl<'a, C: CurveGroup + PartialEq> Mul<&CurvePoint<'a, C>> for &BigUint {
+    type Output = CurvePoint<'a, C>;
+
+    fn mul(self, rhs: &CurvePoint<'a, C>) -> CurvePoint<'a, C> {
+        rhs.scalar_mul(self)
+    }
+}
+
+impl<'a, C: CurveGroup + PartialEq> Mul<CurvePoint<'a, C>> for &BigUint {
+    type Output = CurvePoint<'a, C>;
+
+    fn mul(self, rhs: CurvePoint<'a, C>) -> CurvePoint<'a, C> {
+        rhs.scalar_mul(self)
+    }
+}
+
+impl<'a, C: CurveGroup + PartialEq> Mul<CurvePoint<'a, C>> for BigUint {
+    type Output = CurvePoint<'a, C>;
+
+    fn mul(self, rhs: CurvePoint<'a, C>) -> CurvePoint<'a, C> {
+        rhs.scalar_mul(&self)
+    }
+}
+
+impl<'a, C: CurveGroup + PartialEq> Mul<&BigUint> for &CurvePoint<'a, C> {
+    type Output = CurvePoint<'a, C>;
+
+    fn mul(self, rhs: &BigUint) -> CurvePoint<'a, C> {
+        self.scalar_mul(rhs)
+    }
+}
+
+impl<'a, C: CurveGroup + PartialEq> Mul<&BigUint> for CurvePoint<'a, C> {
+    type Output = CurvePoint<'a, C>;
+
+    fn mul(self, rhs: &BigUint) -> CurvePoint<'a, C> {
+        self.scalar_mul(rhs)
+    }
+}
+
+impl<'a, C: CurveGroup + PartialEq> Sum for CurvePoint<'a, C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::identity(), |acc, p| acc + p)
+    }
+}
+
+impl<'a, 'b, C: CurveGroup + PartialEq> Sum<&'b CurvePoint<'a, C>> for CurvePoint<'a, C> {
+    fn sum<I: Iterator<Item = &'b CurvePoint<'a, C>>>(iter: I) -> Self {
+        iter.fold(Self::identity(), |acc, p| acc + p)
+    }
+}
+
+#[cfg(test)]
+mod curve_point_test {
+    use super::{BigUint, CurvePoint, EccError, EllipticCurve, Point};
+    use crate::curves;
+    use std::collections::HashSet;
+
+    fn get_test_curve() -> EllipticCurve {
+        EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        )
+    }
+
+    #[test]
+    fn test_operators() {
+        let ec = get_test_curve();
+        let g = CurvePoint::new(
+            &ec,
+            Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32)),
+        )
+        .expect("(5, 1) is on the curve");
+        let two = BigUint::from(2u32);
+        let three = BigUint::from(3u32);
+
+        // (5,1) + (5,1) = (6,3)
+        assert_eq!(
+            (&g + &g).point(),
+            &Point::Coordinates(BigUint::from(6u32), BigUint::from(3u32))
+        );
+        assert_eq!(&g + &g, g.double());
+        assert_eq!(&two * &g, &g + &g);
+        assert_eq!(&g * &three, &g + &g + &g);
+        assert_eq!(&three * &g - &g, &two * &g);
+        assert!((&g - &g).is_identity());
+        assert!((&g + -&g).is_identity());
+        assert_eq!(-(-g.clone()), g);
+        assert_eq!(BigUint::from(19u32) * g.clone(), CurvePoint::identity());
+
+        let mut acc = CurvePoint::identity();
+        acc += &g;
+        acc += g.clone();
+        acc -= &g;
+        assert_eq!(acc, g);
+    }
+
+    #[test]
+    fn test_sum_and_hash() {
+        let params = curves::secp256k1();
+        let g = CurvePoint::new(params.curve(), params.generator().clone())
+            .expect("generator is on the curve");
+
+        // 1 G + 2 G + ... + 10 G = 55 G
+        let multiples: Vec<CurvePoint> = (1..=10u32).map(|i| &BigUint::from(i) * &g).collect();
+        assert_eq!(
+            multiples.iter().sum::<CurvePoint>(),
+            &BigUint::from(55u32) * &g
+        );
+        assert_eq!(
+            multiples.into_iter().sum::<CurvePoint>(),
+            &BigUint::from(55u32) * &g
+        );
+        assert_eq!(
+            std::iter::empty::<CurvePoint>().sum::<CurvePoint>(),
+            CurvePoint::identity()
+        );
+
+        let set: HashSet<CurvePoint> = [g.clone(), &g + &g, g.double(), -&g, -(-&g)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn test_errors() {
+        let ec = get_test_curve();
+        let off_curve = Point::Coordinates(BigUint::from(5u32), BigUint::from(2u32));
+        assert_eq!(
+            CurvePoint::new(&ec, off_curve.clone()).err(),
+            Some(EccError::NotOnCurve(off_curve))
+        );
+
+        let params = curves::secp256k1();
+        let g = CurvePoint::new(params.curve(), params.generator().clone())
+            .expect("generator is on the curve");
+        let h = CurvePoint::new(
+            &ec,
+            Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32)),
+        )
+        .expect("(5, 1) is on the curve");
+        assert_eq!(g.try_add(&h).err(), Some(EccError::CurveMismatch));
+        assert_ne!(g, h);
+    }
+}
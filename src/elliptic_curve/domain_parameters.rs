@@ -0,0 +1,385 @@
+use super::curve_group::CurveGroup;
+use super::elliptic_curve::{EllipticCurve, Point};
+use super::glv::GlvEndomorphism;
+use super::primality::{is_prime, trial_division};
+use crate::error::{EccError, Result};
+use num_bigint::{BigInt, BigUint};
+
+// Curve, generator G, its order n and the cofactor h, checked once at
+// construction. Code that takes DomainParameters can rely on all of:
+// the field modulus is prime (irreducible for GF(2^m)), the curve is
+// non-singular, G is on the curve, n is prime, nG = O and h * n lies in
+// the Hasse interval [q + 1 - 2 sqrt(q), q + 1 + 2 sqrt(q)].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainParameters<C: CurveGroup = EllipticCurve> {
+    curve: C,
+    g: Point,
+    n: BigUint,
+    h: BigUint,
+}
+
+impl<C: CurveGroup> DomainParameters<C> {
+    pub fn new(curve: C, g: Point, n: BigUint, h: BigUint) -> Result<Self> {
+        curve.validate()?;
+        if !curve.is_on_curve(&g) {
+            return Err(EccError::GeneratorNotOnCurve);
+        }
+        if !is_prime(&n) {
+            return Err(EccError::OrderNotPrime);
+        }
+        if curve.try_scalar_mul(&g, &n)? != Point::Identity {
+            return Err(EccError::InvalidGeneratorOrder);
+        }
+        if !within_hasse_bound(&curve.field_order(), &(&h * &n)) {
+            return Err(EccError::HasseBoundViolated);
+        }
+        Ok(DomainParameters { curve, g, n, h })
+    }
+
+    pub fn curve(&self) -> &C {
+        &self.curve
+    }
+
+    pub fn generator(&self) -> &Point {
+        &self.g
+    }
+
+    pub fn order(&self) -> &BigUint {
+        &self.n
+    }
+
+    pub fn cofactor(&self) -> &BigUint {
+        &self.h
+    }
+
+    // h = 1 and no other multiple of n fits in the Hasse interval, so
+    // #E = n and every point lies in the subgroup generated by G
+    pub fn has_prime_order(&self) -> bool {
+        self.h == BigUint::from(1u32) && &self.n * &self.n > self.curve.field_order() * 16u32
+    }
+
+    // #E = h n as [(p, e), ...]; n is prime and h is small enough for trial division
+    pub fn group_order_factorization(&self) -> Vec<(BigUint, u32)> {
+        let mut factors = trial_division(&self.h);
+        match factors.iter_mut().find(|(p, _)| p == &self.n) {
+            Some((_, e)) => *e += 1,
+            None => factors.push((self.n.clone(), 1)),
+        }
+        factors
+    }
+
+    pub fn point_order(&self, c: &Point) -> BigUint {
+        self.try_point_order(c).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_point_order(&self, c: &Point) -> Result<BigUint> {
+        self.curve
+            .try_point_order(c, &self.group_order_factorization())
+    }
+
+    pub fn clear_cofactor(&self, c: &Point) -> Point {
+        self.try_clear_cofactor(c)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // h c, a point of the subgroup generated by G
+    pub fn try_clear_cofactor(&self, c: &Point) -> Result<Point> {
+        self.curve.try_clear_cofactor(c, &self.h)
+    }
+
+    // On the curve and of order dividing n. Needs no multiplication when
+    // the whole group has prime order.
+    pub fn is_in_subgroup(&self, c: &Point) -> bool {
+        if self.has_prime_order() {
+            return self.curve.is_on_curve(c);
+        }
+        self.curve.is_in_subgroup(c, &self.n)
+    }
+}
+
+impl DomainParameters<EllipticCurve> {
+    // Attaches a GLV endomorphism to the curve, after which its scalar
+    // multiplications use it. Requires a = 0, beta and lambda non-trivial
+    // cube roots of unity mod p and mod n, a basis of the lattice of lambda
+    // with determinant +-n, phi(G) = lambda G, and a group of prime order,
+    // so that phi is multiplication by lambda on every point.
+    pub fn with_endomorphism(mut self, glv: GlvEndomorphism) -> Result<Self> {
+        let one = BigUint::from(1u32);
+        let p = self.curve.p();
+        let cube_root =
+            |x: &BigUint, m: &BigUint| x != &one && x.modpow(&BigUint::from(3u32), m) == one;
+        if self.curve.a() != &BigUint::from(0u32)
+            || !self.has_prime_order()
+            || !cube_root(glv.beta(), p)
+            || !cube_root(glv.lambda(), &self.n)
+            || glv.order() != &self.n
+            || !glv.basis_in_lattice()
+        {
+            return Err(EccError::InvalidEndomorphism);
+        }
+
+        // lambda G before the endomorphism is there to speed it up
+        let lambda_g = self.curve.try_scalar_mul(&self.g, glv.lambda())?;
+        self.curve.set_endomorphism(glv);
+        if self.curve.apply_endomorphism(&self.g) != lambda_g {
+            return Err(EccError::InvalidEndomorphism);
+        }
+        Ok(self)
+    }
+}
+
+// |#E - (q + 1)| <= 2 sqrt(q), squared to stay in integers
+pub(crate) fn within_hasse_bound(q: &BigUint, points: &BigUint) -> bool {
+    let trace = BigInt::from(q.clone()) + 1 - BigInt::from(points.clone());
+    &trace * &trace <= BigInt::from(q * 4u32)
+}
+
+#[cfg(test)]
+mod domain_parameters_test {
+    use super::{
+        BigUint, CurveGroup, DomainParameters, EccError, EllipticCurve, GlvEndomorphism, Point,
+    };
+    use crate::elliptic_curve::{BinaryCurve, BinaryField};
+
+    fn get_test_curve() -> EllipticCurve {
+        EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        )
+    }
+
+    fn test_generator() -> Point {
+        Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32))
+    }
+
+    #[test]
+    fn test_valid() {
+        let params = DomainParameters::new(
+            get_test_curve(),
+            test_generator(),
+            BigUint::from(19u32),
+            BigUint::from(1u32),
+        )
+        .expect("could not validate test parameters");
+        assert_eq!(params.order(), &BigUint::from(19u32));
+        assert_eq!(params.generator(), &test_generator());
+    }
+
+    #[test]
+    fn test_modulus_not_prime() {
+        // 21 = 3 * 7, rejected before the generator is looked at
+        let curve = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(21u32),
+        );
+        let result = DomainParameters::new(
+            curve,
+            test_generator(),
+            BigUint::from(19u32),
+            BigUint::from(1u32),
+        );
+        assert_eq!(result.err(), Some(EccError::ModulusNotPrime));
+    }
+
+    #[test]
+    fn test_singular_curve() {
+        // y^2 = x^3 - 3x + 2 = (x - 1)^2 (x + 2)
+        let curve = EllipticCurve::new(
+            BigUint::from(14u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+        let result = DomainParameters::new(
+            curve,
+            Point::Coordinates(BigUint::from(1u32), BigUint::from(0u32)),
+            BigUint::from(19u32),
+            BigUint::from(1u32),
+        );
+        assert_eq!(result.err(), Some(EccError::SingularCurve));
+
+        let curve = BinaryCurve::new(
+            BigUint::from(1u32),
+            BigUint::from(0u32),
+            BinaryField::trinomial(4, 1).expect("could not build the field"),
+        );
+        let result = DomainParameters::new(
+            curve,
+            Point::Identity,
+            BigUint::from(2u32),
+            BigUint::from(1u32),
+        );
+        assert_eq!(result.err(), Some(EccError::SingularCurve));
+    }
+
+    #[test]
+    fn test_reducible_modulus() {
+        let curve = BinaryCurve::new(
+            BigUint::from(1u32),
+            BigUint::from(1u32),
+            BinaryField::trinomial(4, 2).expect("could not build the field"),
+        );
+        let result = DomainParameters::new(
+            curve,
+            Point::Identity,
+            BigUint::from(2u32),
+            BigUint::from(1u32),
+        );
+        assert_eq!(result.err(), Some(EccError::ReducibleModulus));
+    }
+
+    #[test]
+    fn test_generator_checks() {
+        let result = DomainParameters::new(
+            get_test_curve(),
+            Point::Coordinates(BigUint::from(5u32), BigUint::from(2u32)),
+            BigUint::from(19u32),
+            BigUint::from(1u32),
+        );
+        assert_eq!(result.err(), Some(EccError::GeneratorNotOnCurve));
+
+        let result = DomainParameters::new(
+            get_test_curve(),
+            test_generator(),
+            BigUint::from(18u32),
+            BigUint::from(1u32),
+        );
+        assert_eq!(result.err(), Some(EccError::OrderNotPrime));
+
+        let result = DomainParameters::new(
+            get_test_curve(),
+            test_generator(),
+            BigUint::from(17u32),
+            BigUint::from(1u32),
+        );
+        assert_eq!(result.err(), Some(EccError::InvalidGeneratorOrder));
+    }
+
+    #[test]
+    fn test_hasse_bound() {
+        // 2 * 19 = 38 points is more than 17 + 1 + 2 sqrt(17) ~ 26.2
+        let result = DomainParameters::new(
+            get_test_curve(),
+            test_generator(),
+            BigUint::from(19u32),
+            BigUint::from(2u32),
+        );
+        assert_eq!(result.err(), Some(EccError::HasseBoundViolated));
+    }
+
+    #[test]
+    fn test_invalid_endomorphism() {
+        let secp256k1 = crate::curves::secp256k1();
+        let glv = secp256k1
+            .curve()
+            .endomorphism()
+            .expect("secp256k1 has an endomorphism")
+            .clone();
+        let plain = |params: &DomainParameters| {
+            let c = params.curve();
+            DomainParameters::new(
+                EllipticCurve::new(c.a().clone(), c.b().clone(), c.p().clone()),
+                params.generator().clone(),
+                params.order().clone(),
+                params.cofactor().clone(),
+            )
+            .expect("could not validate parameters")
+        };
+        assert_eq!(
+            plain(secp256k1).with_endomorphism(glv.clone()).as_ref(),
+            Ok(secp256k1)
+        );
+
+        // the other cube root of unity mod n does not match beta
+        let n = secp256k1.order();
+        let (v1, v2) = glv.basis();
+        let (v1, v2) = (v1.clone(), v2.clone());
+        let lambda2 = glv.lambda() * glv.lambda() % n;
+        let wrong = GlvEndomorphism::new(glv.beta().clone(), lambda2, v1, v2);
+        assert_eq!(
+            plain(secp256k1).with_endomorphism(wrong).err(),
+            Some(EccError::InvalidEndomorphism)
+        );
+
+        // P-256 has a != 0
+        assert_eq!(
+            plain(crate::curves::secp256r1())
+                .with_endomorphism(glv)
+                .err(),
+            Some(EccError::InvalidEndomorphism)
+        );
+    }
+
+    #[test]
+    fn test_point_order_and_subgroup() {
+        // y^2 = x^3 + 2x + 3 over F_17 has 22 = 2 * 11 points
+        let params = DomainParameters::new(
+            EllipticCurve::new(
+                BigUint::from(2u32),
+                BigUint::from(3u32),
+                BigUint::from(17u32),
+            ),
+            Point::Coordinates(BigUint::from(3u32), BigUint::from(6u32)),
+            BigUint::from(11u32),
+            BigUint::from(2u32),
+        )
+        .expect("could not validate test parameters");
+        assert!(!params.has_prime_order());
+        assert_eq!(
+            params.group_order_factorization(),
+            vec![(BigUint::from(2u32), 1), (BigUint::from(11u32), 1)]
+        );
+
+        let full = Point::Coordinates(BigUint::from(2u32), BigUint::from(7u32));
+        let two_torsion = Point::Coordinates(BigUint::from(16u32), BigUint::from(0u32));
+        assert_eq!(params.point_order(params.generator()), BigUint::from(11u32));
+        assert_eq!(params.point_order(&full), BigUint::from(22u32));
+        assert_eq!(params.point_order(&two_torsion), BigUint::from(2u32));
+        assert_eq!(params.point_order(&Point::Identity), BigUint::from(1u32));
+
+        assert!(params.is_in_subgroup(params.generator()));
+        assert!(params.is_in_subgroup(&Point::Identity));
+        assert!(!params.is_in_subgroup(&full));
+        assert!(!params.is_in_subgroup(&two_torsion));
+
+        let cleared = params.clear_cofactor(&full);
+        assert!(params.is_in_subgroup(&cleared));
+        assert_eq!(params.point_order(&cleared), BigUint::from(11u32));
+        assert_eq!(params.clear_cofactor(&two_torsion), Point::Identity);
+
+        // 11 is not a multiple of the order of (2, 7)
+        assert_eq!(
+            params
+                .curve()
+                .try_point_order(&full, &[(BigUint::from(11u32), 1)]),
+            Err(EccError::InvalidPointOrder)
+        );
+    }
+
+    #[test]
+    fn test_prime_order_curves() {
+        let params = crate::curves::secp256k1();
+        assert!(params.has_prime_order());
+        assert_eq!(params.point_order(params.generator()), *params.order());
+        assert!(params.is_in_subgroup(params.generator()));
+
+        // h = 1 is not enough when another multiple of n fits the Hasse
+        // interval: 11 passes the bound, but y^2 = x^3 + 2x + 3 over F_17
+        // really has 22 points
+        let params = DomainParameters::new(
+            EllipticCurve::new(
+                BigUint::from(2u32),
+                BigUint::from(3u32),
+                BigUint::from(17u32),
+            ),
+            Point::Coordinates(BigUint::from(3u32), BigUint::from(6u32)),
+            BigUint::from(11u32),
+            BigUint::from(1u32),
+        )
+        .expect("could not validate test parameters");
+        assert!(!params.has_prime_order());
+        let full = Point::Coordinates(BigUint::from(2u32), BigUint::from(7u32));
+        assert!(!params.is_in_subgroup(&full));
+    }
+}
@@ -1,8 +1,13 @@
+use super::field_element::FieldElement;
 use super::finite_field::FiniteField;
+use super::glv::GlvEndomorphism;
+use super::primality::is_prime;
+use super::wnaf::DEFAULT_WNAF_WIDTH;
+use crate::error::{EccError, Result};
 use num_bigint::BigUint;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Point {
     Coordinates(BigUint, BigUint),
     Identity,
@@ -17,130 +22,186 @@ impl Display for Point {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EllipticCurve {
-    // y^2=x^2+a*x+b
-    pub a: BigUint,
-    pub b: BigUint,
-    pub p: BigUint,
+    // y^2=x^3+a*x+b
+    a: BigUint,
+    b: BigUint,
+    // base field of order p, built once so its backend constants are reused
+    field: FiniteField,
+    // GLV endomorphism, set only by DomainParameters::with_endomorphism
+    pub(super) glv: Option<Box<GlvEndomorphism>>,
 }
 
-impl PartialEq for Point {
-    fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Point::Identity, Point::Identity) => true,
-            (Point::Coordinates(x1, y1), Point::Coordinates(x2, y2)) => x1 == x2 && y1 == y2,
-            (_, _) => false,
+impl EllipticCurve {
+    pub fn new(a: BigUint, b: BigUint, p: BigUint) -> Self {
+        EllipticCurve {
+            a,
+            b,
+            field: FiniteField::new(p),
+            glv: None,
         }
     }
-}
 
-impl EllipticCurve {
-    pub fn add(&self, r: &Point, q: &Point) -> Point {
-        assert!(self.is_on_curve(r), "Point {} is not on curve", r);
-        assert!(self.is_on_curve(q), "Point {} is not on curve", q);
-        assert_ne!(r, q, "Points should not be the same");
-
-        match (r, q) {
-            (Point::Identity, Point::Coordinates(x, y)) => Point::Coordinates(x.clone(), y.clone()),
-            (Point::Coordinates(x, y), Point::Identity) => Point::Coordinates(x.clone(), y.clone()),
-            (Point::Coordinates(x1, y1), Point::Coordinates(x2, y2)) => {
-                let f = FiniteField { p: self.p.clone() };
-
-                // logic for reflected points
-                let y_sum = f.add(y1, y2);
-                if x1 == x2 && y_sum == BigUint::from(0u32) {
-                    return Point::Identity;
-                }
+    pub fn a(&self) -> &BigUint {
+        &self.a
+    }
+
+    pub fn b(&self) -> &BigUint {
+        &self.b
+    }
 
-                // lambda = (y2 - y1) / (x2 - x1)
-                let d_y = f.sub(y2, y1);
-                let d_x = f.sub(x2, x1);
-                let lambda = f.div(&d_y, &d_x);
+    pub fn p(&self) -> &BigUint {
+        self.field.p()
+    }
 
-                let (x3, y3) = self.calculate_x3_y3(&lambda, x1, x2, y1);
-                Point::Coordinates(x3, y3)
-            }
-            (Point::Identity, Point::Identity) => Point::Identity,
+    pub fn field(&self) -> &FiniteField {
+        &self.field
+    }
+
+    // p must be prime and 4a^3 + 27b^2 != 0 (mod p)
+    pub fn validate(&self) -> Result<()> {
+        if !is_prime(self.p()) {
+            return Err(EccError::ModulusNotPrime);
+        }
+        let f = &self.field;
+        let (a, b) = (f.element(&self.a), f.element(&self.b));
+        let discriminant = f.element(&BigUint::from(4u32)) * a.square() * &a
+            + f.element(&BigUint::from(27u32)) * b.square();
+        if discriminant.is_zero() {
+            return Err(EccError::SingularCurve);
         }
+        Ok(())
+    }
+
+    pub fn add(&self, r: &Point, q: &Point) -> Point {
+        self.try_add(r, q).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // Complete addition: r == q, r == -q and the identity need no branches
+    // on curves of odd order.
+    pub fn try_add(&self, r: &Point, q: &Point) -> Result<Point> {
+        self.check_on_curve(r)?;
+        self.check_on_curve(q)?;
+
+        let sum = self.complete_add(&self.to_projective(r), &self.to_projective(q));
+        // on curves of even order the formulas give (0 : 0 : 0) when r - q
+        // has order 2; the Jacobian law handles that case
+        if sum.x().is_zero() && sum.y().is_zero() && sum.z().is_zero() {
+            let sum = self.jacobian_add(&self.to_jacobian(r), &self.to_jacobian(q));
+            return self.try_to_affine(&sum);
+        }
+        self.try_projective_to_affine(&sum)
     }
 
     pub fn double(&self, c: &Point) -> Point {
-        assert!(self.is_on_curve(c), "Point {} is not on curve", c);
+        self.try_double(c).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_double(&self, c: &Point) -> Result<Point> {
+        self.check_on_curve(c)?;
 
         match c {
-            Point::Identity => Point::Identity,
+            Point::Identity => Ok(Point::Identity),
             Point::Coordinates(x, y) => {
                 // if P = Q, y = y => 2P = e
                 if y == &BigUint::from(0u32) {
-                    return Point::Identity;
+                    return Ok(Point::Identity);
                 }
 
-                let f = FiniteField { p: self.p.clone() };
+                let f = &self.field;
+                let (x, y) = (f.element(x), f.element(y));
 
                 // lambda = (3x^2 + a) / 2y
-                let x_sq = f.mul(x, x);
-                let numerator = f.add(&f.mul(&x_sq, &BigUint::from(3u32)), &self.a);
-                let denominator = f.mul(&BigUint::from(2u32), y);
-                let lambda = f.div(&numerator, &denominator);
+                let numerator = x.square() * f.element(&BigUint::from(3u32)) + f.element(&self.a);
+                let denominator = f.element(&BigUint::from(2u32)) * &y;
+                let lambda = numerator.try_div(&denominator)?;
 
-                let (x2, y2) = self.calculate_x3_y3(&lambda, x, x, y);
-                Point::Coordinates(x2, y2)
+                let (x2, y2) = self.calculate_x3_y3(&lambda, &x, &x, &y);
+                Ok(Point::Coordinates(x2.value(), y2.value()))
             }
         }
     }
 
-    pub fn calculate_x3_y3(
+    pub fn calculate_x3_y3<'a>(
         &self,
-        lambda: &BigUint,
-        x1: &BigUint,
-        x2: &BigUint,
-        y1: &BigUint,
-    ) -> (BigUint, BigUint) {
-        let f = FiniteField { p: self.p.clone() };
-
-        let lambda_sq = f.mul(&lambda, &lambda);
+        lambda: &FieldElement<'a>,
+        x1: &FieldElement<'a>,
+        x2: &FieldElement<'a>,
+        y1: &FieldElement<'a>,
+    ) -> (FieldElement<'a>, FieldElement<'a>) {
         // x3 = lambda^2 - x1 -x2 (mod p)
-        let x3 = f.sub(&f.sub(&lambda_sq, x1), x2);
+        let x3 = lambda.square() - x1 - x2;
         // y3 = lambda(x1 - x3) - y1 (mod p)
-        let y3 = f.sub(&f.mul(&lambda, &f.sub(x1, &x3)), y1);
+        let y3 = lambda * (x1 - &x3) - y1;
         (x3, y3)
     }
 
+    // -(x, y) = (x, -y)
+    pub fn negate(&self, c: &Point) -> Point {
+        match c {
+            Point::Coordinates(x, y) => {
+                Point::Coordinates(x.clone(), (self.p() - y % self.p()) % self.p())
+            }
+            Point::Identity => Point::Identity,
+        }
+    }
+
     pub fn scalar_mul(&self, c: &Point, d: &BigUint) -> Point {
-        assert!(self.is_on_curve(c), "Point {} is not on curve", c);
+        self.try_scalar_mul(c, d)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        let mut t = (*c).clone();
-        for i in (0..(d.bits() - 1)).rev() {
-            t = self.double(&t);
-            if d.bit(i) {
-                t = self.add(&t, &c);
-            }
+    pub fn try_scalar_mul(&self, c: &Point, d: &BigUint) -> Result<Point> {
+        self.check_on_curve(c)?;
+        if d == &BigUint::from(0u32) {
+            return Ok(Point::Identity);
+        }
+
+        // half-length scalars and one shared doubling chain when the curve
+        // has an endomorphism
+        if self.endomorphism().is_some() {
+            return self
+                .try_multi_scalar_mul_windowed(&[(c.clone(), d.clone())], DEFAULT_WNAF_WIDTH);
+        }
+        // inversion-free in Jacobian coordinates, one conversion at the end
+        self.jacobian_scalar_mul(c, d)
+    }
+
+    fn check_on_curve(&self, c: &Point) -> Result<()> {
+        if !self.is_on_curve(c) {
+            return Err(EccError::NotOnCurve(c.clone()));
         }
-        t
+        Ok(())
     }
 
     pub fn is_on_curve(&self, c: &Point) -> bool {
         match c {
             Point::Coordinates(x, y) => {
-                let y_sq = y.modpow(&BigUint::from(2u32), &self.p);
-                let x_cb = x.modpow(&BigUint::from(3u32), &self.p);
-                y_sq == (x_cb + &self.a * x + &self.b).modpow(&BigUint::from(1u32), &self.p)
+                if x >= self.p() || y >= self.p() {
+                    return false;
+                }
+                let f = &self.field;
+                let (x, y) = (f.element(x), f.element(y));
+                // y^2 = x^3 + a*x + b
+                y.square() == x.square() * &x + f.element(&self.a) * &x + f.element(&self.b)
             }
-            Point::Identity => return true,
+            Point::Identity => true,
         }
     }
 }
 
+#[cfg(test)]
 mod ec_test {
-    use super::{BigUint, EllipticCurve, FiniteField, Point};
+    use super::{BigUint, EccError, EllipticCurve, Point};
 
     #[test]
     fn test_ec_point_addition() {
-        let ec = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(2u32),
-            p: BigUint::from(17u32),
-        };
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
 
         // (6,3) + (5,1) = (10, 6)
         let p1 = Point::Coordinates(BigUint::from(6u32), BigUint::from(3u32));
@@ -163,37 +224,58 @@ mod ec_test {
 
         // Reflected points
         // (6,3) + (6,-3) = e
-        let f = FiniteField {
-            p: BigUint::from(17u32),
-        };
         let p1 = Point::Coordinates(BigUint::from(6u32), BigUint::from(3u32));
-        let p2 = Point::Coordinates(BigUint::from(6u32), f.inv_add(&BigUint::from(3u32)));
+        let p2 = ec.negate(&p1);
+        assert_eq!(
+            p2,
+            Point::Coordinates(BigUint::from(6u32), BigUint::from(14u32))
+        );
         let sum = ec.add(&p1, &p2);
         assert_eq!(sum, Point::Identity);
     }
 
     #[test]
-    #[should_panic]
-    fn test_ec_point_addition_same_points_assertion() {
-        let ec = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(2u32),
-            p: BigUint::from(17u32),
-        };
+    fn test_ec_point_addition_even_order() {
+        // y^2 = x^3 - x over F_17 has three points of order 2
+        let ec = EllipticCurve::new(
+            BigUint::from(16u32),
+            BigUint::from(0u32),
+            BigUint::from(17u32),
+        );
+        let p1 = Point::Coordinates(BigUint::from(0u32), BigUint::from(0u32));
+        let p2 = Point::Coordinates(BigUint::from(1u32), BigUint::from(0u32));
+        let r = Point::Coordinates(BigUint::from(16u32), BigUint::from(0u32));
+        assert_eq!(ec.add(&p1, &p2), r);
+        assert_eq!(ec.add(&p1, &p1), Point::Identity);
+    }
 
-        let p1 = Point::Identity;
-        let p2 = Point::Identity;
-        let _ = ec.add(&p1, &p2);
+    #[test]
+    fn test_ec_point_addition_same_points() {
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+
+        // e + e = e
+        let sum = ec.add(&Point::Identity, &Point::Identity);
+        assert_eq!(sum, Point::Identity);
+
+        // (5,1) + (5,1) = 2 (5,1) = (6,3)
+        let p1 = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
+        let r = Point::Coordinates(BigUint::from(6u32), BigUint::from(3u32));
+        assert_eq!(ec.add(&p1, &p1), r);
+        assert_eq!(ec.add(&p1, &ec.negate(&p1)), Point::Identity);
     }
 
     #[test]
     #[should_panic]
     fn test_ec_point_addition_p1_not_on_curve_assertion() {
-        let ec = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(2u32),
-            p: BigUint::from(17u32),
-        };
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
 
         let p1 = Point::Coordinates(BigUint::from(63u32), BigUint::from(3u32));
         let p2 = Point::Identity;
@@ -203,24 +285,48 @@ mod ec_test {
     #[test]
     #[should_panic]
     fn test_ec_point_addition_p2_not_on_curve_assertion() {
-        let ec = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(2u32),
-            p: BigUint::from(17u32),
-        };
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
 
         let p1 = Point::Identity;
         let p2 = Point::Coordinates(BigUint::from(63u32), BigUint::from(3u32));
         let _ = ec.add(&p1, &p2);
     }
 
+    #[test]
+    fn test_try_add_errors() {
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+
+        let off_curve = Point::Coordinates(BigUint::from(63u32), BigUint::from(3u32));
+        let p1 = Point::Coordinates(BigUint::from(6u32), BigUint::from(3u32));
+        assert_eq!(
+            ec.try_add(&p1, &off_curve),
+            Err(EccError::NotOnCurve(off_curve.clone()))
+        );
+        assert_eq!(
+            ec.try_double(&off_curve),
+            Err(EccError::NotOnCurve(off_curve.clone()))
+        );
+        assert_eq!(
+            ec.try_scalar_mul(&off_curve, &BigUint::from(2u32)),
+            Err(EccError::NotOnCurve(off_curve))
+        );
+    }
+
     #[test]
     fn test_point_doubling() {
-        let ec = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(2u32),
-            p: BigUint::from(17u32),
-        };
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
 
         // (5,1) + (5,1) = (6,3)
         let p1 = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
@@ -237,22 +343,22 @@ mod ec_test {
     #[test]
     #[should_panic]
     fn test_point_doubling_on_curve_assertion() {
-        let ec = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(2u32),
-            p: BigUint::from(17u32),
-        };
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
         let p2 = Point::Coordinates(BigUint::from(63u32), BigUint::from(3u32));
         let _ = ec.double(&p2);
     }
 
     #[test]
     fn test_scalar_mul() {
-        let ec = EllipticCurve {
-            a: BigUint::from(2u32),
-            b: BigUint::from(2u32),
-            p: BigUint::from(17u32),
-        };
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
 
         // 2 (5,1) = (6,3)
         let p1 = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
@@ -270,7 +376,12 @@ mod ec_test {
         let p1 = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
         let r = Point::Identity;
         let product = ec.scalar_mul(&p1, &BigUint::from(19u32));
-        assert_eq!(r, product)
+        assert_eq!(r, product);
+
+        // 0 (5,1) = e
+        let p1 = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
+        let product = ec.scalar_mul(&p1, &BigUint::from(0u32));
+        assert_eq!(Point::Identity, product)
     }
 
     #[test]
@@ -319,7 +430,7 @@ mod ec_test {
         )
         .expect("could not convert str to gy");
 
-        let ec = EllipticCurve { a, b, p };
+        let ec = EllipticCurve::new(a, b, p);
         let g = Point::Coordinates(gx, gy);
         let res = ec.scalar_mul(&g, &n);
         // n * g = I, n is an order of the group
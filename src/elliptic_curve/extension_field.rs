@@ -0,0 +1,460 @@
+use super::field::Field;
+use super::finite_field::FiniteField;
+use crate::error::Result;
+use num_bigint::BigUint;
+
+// c0 + c1 * w with w^2 = non_residue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuadraticElement<E> {
+    pub c0: E,
+    pub c1: E,
+}
+
+// c0 + c1 * v + c2 * v^2 with v^3 = non_residue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CubicElement<E> {
+    pub c0: E,
+    pub c1: E,
+    pub c2: E,
+}
+
+// F[w] / (w^2 - non_residue) over any base field F
+#[derive(Debug, Clone)]
+pub struct QuadraticExtension<F: Field> {
+    base: F,
+    non_residue: F::Element,
+    // w^(p - 1) = non_residue^((p - 1) / 2), so that (c1 w)^p = c1^p * coeff * w
+    frobenius_coeff: F::Element,
+}
+
+// F[v] / (v^3 - non_residue) over any base field F
+#[derive(Debug, Clone)]
+pub struct CubicExtension<F: Field> {
+    base: F,
+    non_residue: F::Element,
+    // v^(p - 1) and v^(2(p - 1)), i.e. non_residue^((p - 1) / 3) and its square
+    frobenius_coeff_c1: F::Element,
+    frobenius_coeff_c2: F::Element,
+}
+
+// The tower used by BN and BLS curves:
+// Fp2 = Fp[u] / (u^2 - beta), Fp6 = Fp2[v] / (v^3 - xi), Fp12 = Fp6[w] / (w^2 - v)
+pub type Fp2 = QuadraticExtension<FiniteField>;
+pub type Fp6 = CubicExtension<Fp2>;
+pub type Fp12 = QuadraticExtension<Fp6>;
+
+pub type Fp2Element = QuadraticElement<BigUint>;
+pub type Fp6Element = CubicElement<Fp2Element>;
+pub type Fp12Element = QuadraticElement<Fp6Element>;
+
+// Builds Fp12 from p, the Fp2 non-residue beta and the Fp6 non-residue xi in Fp2.
+pub fn tower(p: BigUint, beta: BigUint, xi: Fp2Element) -> Fp12 {
+    let fp2 = QuadraticExtension::new(FiniteField::new(p), beta);
+    let fp6 = CubicExtension::new(fp2, xi);
+    // w^2 = v
+    let v = CubicElement {
+        c0: fp6.base().zero(),
+        c1: fp6.base().one(),
+        c2: fp6.base().zero(),
+    };
+    QuadraticExtension::new(fp6, v)
+}
+
+impl<F: Field> QuadraticExtension<F> {
+    // non_residue must not be a square in the base field
+    pub fn new(base: F, non_residue: F::Element) -> Self {
+        let exponent = (base.characteristic() - BigUint::from(1u32)) >> 1;
+        let frobenius_coeff = base.pow(&non_residue, &exponent);
+        QuadraticExtension {
+            base,
+            non_residue,
+            frobenius_coeff,
+        }
+    }
+
+    pub fn base(&self) -> &F {
+        &self.base
+    }
+
+    pub fn non_residue(&self) -> &F::Element {
+        &self.non_residue
+    }
+
+    pub fn frobenius_coeff(&self) -> &F::Element {
+        &self.frobenius_coeff
+    }
+
+    pub fn element(&self, c0: F::Element, c1: F::Element) -> QuadraticElement<F::Element> {
+        QuadraticElement { c0, c1 }
+    }
+
+    // c0 + c1 w -> c0 - c1 w
+    pub fn conjugate(&self, a: &QuadraticElement<F::Element>) -> QuadraticElement<F::Element> {
+        QuadraticElement {
+            c0: a.c0.clone(),
+            c1: self.base.neg(&a.c1),
+        }
+    }
+
+    fn mul_by_non_residue(&self, a: &F::Element) -> F::Element {
+        self.base.mul(a, &self.non_residue)
+    }
+}
+
+impl<F: Field> Field for QuadraticExtension<F> {
+    type Element = QuadraticElement<F::Element>;
+
+    fn characteristic(&self) -> &BigUint {
+        self.base.characteristic()
+    }
+
+    fn zero(&self) -> Self::Element {
+        self.element(self.base.zero(), self.base.zero())
+    }
+
+    fn one(&self) -> Self::Element {
+        self.element(self.base.one(), self.base.zero())
+    }
+
+    fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        self.element(self.base.add(&a.c0, &b.c0), self.base.add(&a.c1, &b.c1))
+    }
+
+    fn sub(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        self.element(self.base.sub(&a.c0, &b.c0), self.base.sub(&a.c1, &b.c1))
+    }
+
+    fn neg(&self, a: &Self::Element) -> Self::Element {
+        self.element(self.base.neg(&a.c0), self.base.neg(&a.c1))
+    }
+
+    // Karatsuba: c1 = (a0 + a1)(b0 + b1) - a0 b0 - a1 b1
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        let v0 = self.base.mul(&a.c0, &b.c0);
+        let v1 = self.base.mul(&a.c1, &b.c1);
+        let c0 = self.base.add(&v0, &self.mul_by_non_residue(&v1));
+        let c1 = self.base.sub(
+            &self.base.sub(
+                &self
+                    .base
+                    .mul(&self.base.add(&a.c0, &a.c1), &self.base.add(&b.c0, &b.c1)),
+                &v0,
+            ),
+            &v1,
+        );
+        self.element(c0, c1)
+    }
+
+    // (a0 + a1 w)^2 = a0^2 + nr a1^2 + 2 a0 a1 w
+    fn square(&self, a: &Self::Element) -> Self::Element {
+        let c0 = self.base.add(
+            &self.base.square(&a.c0),
+            &self.mul_by_non_residue(&self.base.square(&a.c1)),
+        );
+        let c1 = self.base.double(&self.base.mul(&a.c0, &a.c1));
+        self.element(c0, c1)
+    }
+
+    // (a0 + a1 w)^(-1) = (a0 - a1 w) / (a0^2 - nr a1^2)
+    fn inv(&self, a: &Self::Element) -> Result<Self::Element> {
+        let norm = self.base.sub(
+            &self.base.square(&a.c0),
+            &self.mul_by_non_residue(&self.base.square(&a.c1)),
+        );
+        let norm_inv = self.base.inv(&norm)?;
+        Ok(self.element(
+            self.base.mul(&a.c0, &norm_inv),
+            self.base.neg(&self.base.mul(&a.c1, &norm_inv)),
+        ))
+    }
+
+    fn frobenius(&self, a: &Self::Element) -> Self::Element {
+        self.element(
+            self.base.frobenius(&a.c0),
+            self.base
+                .mul(&self.base.frobenius(&a.c1), &self.frobenius_coeff),
+        )
+    }
+}
+
+impl<F: Field> CubicExtension<F> {
+    // non_residue must not be a cube in the base field
+    pub fn new(base: F, non_residue: F::Element) -> Self {
+        let exponent = (base.characteristic() - BigUint::from(1u32)) / BigUint::from(3u32);
+        let frobenius_coeff_c1 = base.pow(&non_residue, &exponent);
+        let frobenius_coeff_c2 = base.square(&frobenius_coeff_c1);
+        CubicExtension {
+            base,
+            non_residue,
+            frobenius_coeff_c1,
+            frobenius_coeff_c2,
+        }
+    }
+
+    pub fn base(&self) -> &F {
+        &self.base
+    }
+
+    pub fn non_residue(&self) -> &F::Element {
+        &self.non_residue
+    }
+
+    pub fn frobenius_coeffs(&self) -> (&F::Element, &F::Element) {
+        (&self.frobenius_coeff_c1, &self.frobenius_coeff_c2)
+    }
+
+    pub fn element(
+        &self,
+        c0: F::Element,
+        c1: F::Element,
+        c2: F::Element,
+    ) -> CubicElement<F::Element> {
+        CubicElement { c0, c1, c2 }
+    }
+
+    fn mul_by_non_residue(&self, a: &F::Element) -> F::Element {
+        self.base.mul(a, &self.non_residue)
+    }
+}
+
+impl<F: Field> Field for CubicExtension<F> {
+    type Element = CubicElement<F::Element>;
+
+    fn characteristic(&self) -> &BigUint {
+        self.base.characteristic()
+    }
+
+    fn zero(&self) -> Self::Element {
+        self.element(self.base.zero(), self.base.zero(), self.base.zero())
+    }
+
+    fn one(&self) -> Self::Element {
+        self.element(self.base.one(), self.base.zero(), self.base.zero())
+    }
+
+    fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        self.element(
+            self.base.add(&a.c0, &b.c0),
+            self.base.add(&a.c1, &b.c1),
+            self.base.add(&a.c2, &b.c2),
+        )
+    }
+
+    fn sub(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        self.element(
+            self.base.sub(&a.c0, &b.c0),
+            self.base.sub(&a.c1, &b.c1),
+            self.base.sub(&a.c2, &b.c2),
+        )
+    }
+
+    fn neg(&self, a: &Self::Element) -> Self::Element {
+        self.element(
+            self.base.neg(&a.c0),
+            self.base.neg(&a.c1),
+            self.base.neg(&a.c2),
+        )
+    }
+
+    // schoolbook product, reducing v^3 = nr and v^4 = nr v
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        let f = &self.base;
+        let v0 = f.mul(&a.c0, &b.c0);
+        let v1 = f.mul(&a.c1, &b.c1);
+        let v2 = f.mul(&a.c2, &b.c2);
+
+        // c0 = a0 b0 + nr (a1 b2 + a2 b1)
+        let c0 = f.add(
+            &v0,
+            &self.mul_by_non_residue(&f.add(&f.mul(&a.c1, &b.c2), &f.mul(&a.c2, &b.c1))),
+        );
+        // c1 = a0 b1 + a1 b0 + nr a2 b2
+        let c1 = f.add(
+            &f.add(&f.mul(&a.c0, &b.c1), &f.mul(&a.c1, &b.c0)),
+            &self.mul_by_non_residue(&v2),
+        );
+        // c2 = a0 b2 + a1 b1 + a2 b0
+        let c2 = f.add(&f.add(&f.mul(&a.c0, &b.c2), &v1), &f.mul(&a.c2, &b.c0));
+        self.element(c0, c1, c2)
+    }
+
+    fn inv(&self, a: &Self::Element) -> Result<Self::Element> {
+        let f = &self.base;
+        // t0 = a0^2 - nr a1 a2, t1 = nr a2^2 - a0 a1, t2 = a1^2 - a0 a2
+        let t0 = f.sub(
+            &f.square(&a.c0),
+            &self.mul_by_non_residue(&f.mul(&a.c1, &a.c2)),
+        );
+        let t1 = f.sub(
+            &self.mul_by_non_residue(&f.square(&a.c2)),
+            &f.mul(&a.c0, &a.c1),
+        );
+        let t2 = f.sub(&f.square(&a.c1), &f.mul(&a.c0, &a.c2));
+
+        // a * (t0 + t1 v + t2 v^2) = a0 t0 + nr (a2 t1 + a1 t2)
+        let norm = f.add(
+            &f.mul(&a.c0, &t0),
+            &self.mul_by_non_residue(&f.add(&f.mul(&a.c2, &t1), &f.mul(&a.c1, &t2))),
+        );
+        let norm_inv = f.inv(&norm)?;
+        Ok(self.element(
+            f.mul(&t0, &norm_inv),
+            f.mul(&t1, &norm_inv),
+            f.mul(&t2, &norm_inv),
+        ))
+    }
+
+    fn frobenius(&self, a: &Self::Element) -> Self::Element {
+        self.element(
+            self.base.frobenius(&a.c0),
+            self.base
+                .mul(&self.base.frobenius(&a.c1), &self.frobenius_coeff_c1),
+            self.base
+                .mul(&self.base.frobenius(&a.c2), &self.frobenius_coeff_c2),
+        )
+    }
+}
+
+#[cfg(test)]
+mod extension_field_test {
+    use super::{tower, BigUint, CubicElement, Field, Fp12, Fp12Element, QuadraticElement};
+
+    fn int(s: &str) -> BigUint {
+        BigUint::parse_bytes(s.as_bytes(), 10).expect("could not convert str to int")
+    }
+
+    // BN254 (alt_bn128): u^2 = -1, xi = 9 + u
+    fn bn254_tower() -> Fp12 {
+        let p =
+            int("21888242871839275222246405745257275088696311157297823662689037894645226208583");
+        let beta = &p - BigUint::from(1u32);
+        let xi = QuadraticElement {
+            c0: BigUint::from(9u32),
+            c1: BigUint::from(1u32),
+        };
+        tower(p, beta, xi)
+    }
+
+    fn sample_fp12(fp12: &Fp12, seed: u32) -> Fp12Element {
+        let fp2 = |a: u32, b: u32| QuadraticElement {
+            c0: BigUint::from(a) * BigUint::from(seed) + BigUint::from(7u32),
+            c1: BigUint::from(b) * BigUint::from(seed) + BigUint::from(11u32),
+        };
+        let fp6 = |a: u32| CubicElement {
+            c0: fp2(a, a + 1),
+            c1: fp2(a + 2, a + 3),
+            c2: fp2(a + 4, a + 5),
+        };
+        fp12.element(fp6(1), fp6(100))
+    }
+
+    #[test]
+    fn test_published_frobenius_coefficients() {
+        let fp12 = bn254_tower();
+        let fp6 = fp12.base();
+        let fp2 = fp6.base();
+
+        // u^(p - 1) = -1
+        assert_eq!(
+            fp2.frobenius_coeff(),
+            &(fp2.characteristic() - BigUint::from(1u32))
+        );
+
+        // xi^((p - 1) / 3)
+        let (c1, c2) = fp6.frobenius_coeffs();
+        assert_eq!(
+            c1,
+            &QuadraticElement {
+                c0: int(
+                    "21575463638280843010398324269430826099269044274347216827212613867836435027261"
+                ),
+                c1: int(
+                    "10307601595873709700152284273816112264069230130616436755625194854815875713954"
+                ),
+            }
+        );
+        // xi^(2(p - 1) / 3)
+        assert_eq!(
+            c2,
+            &QuadraticElement {
+                c0: int(
+                    "2581911344467009335267311115468803099551665605076196740867805258568234346338"
+                ),
+                c1: int(
+                    "19937756971775647987995932169929341994314640652964949448313374472400716661030"
+                ),
+            }
+        );
+
+        // xi^((p - 1) / 6)
+        let expected = QuadraticElement {
+            c0: int("8376118865763821496583973867626364092589906065868298776909617916018768340080"),
+            c1: int(
+                "16469823323077808223889137241176536799009286646108169935659301613961712198316",
+            ),
+        };
+        assert_eq!(
+            fp12.frobenius_coeff(),
+            &CubicElement {
+                c0: expected,
+                c1: fp2.zero(),
+                c2: fp2.zero(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_field_axioms() {
+        let fp12 = bn254_tower();
+        let a = sample_fp12(&fp12, 3);
+        let b = sample_fp12(&fp12, 5);
+
+        assert_eq!(fp12.mul(&a, &fp12.one()), a);
+        assert_eq!(fp12.add(&a, &fp12.neg(&a)), fp12.zero());
+        assert_eq!(fp12.square(&a), fp12.mul(&a, &a));
+        assert_eq!(fp12.mul(&a, &b), fp12.mul(&b, &a));
+        assert_eq!(fp12.sub(&fp12.add(&a, &b), &b), a);
+
+        let a_inv = fp12.inv(&a).expect("a is non-zero");
+        assert_eq!(fp12.mul(&a, &a_inv), fp12.one());
+        assert!(fp12.inv(&fp12.zero()).is_err());
+
+        let fp6 = fp12.base();
+        let c = a.c1.clone();
+        assert_eq!(fp6.square(&c), fp6.mul(&c, &c));
+        assert_eq!(fp6.mul(&c, &fp6.inv(&c).expect("c is non-zero")), fp6.one());
+    }
+
+    #[test]
+    fn test_frobenius_is_p_power() {
+        let fp12 = bn254_tower();
+        let p = fp12.characteristic().clone();
+        let a = sample_fp12(&fp12, 3);
+
+        let fp6 = fp12.base();
+        let fp2 = fp6.base();
+        assert_eq!(fp2.frobenius(&a.c0.c0), fp2.pow(&a.c0.c0, &p));
+        assert_eq!(fp6.frobenius(&a.c0), fp6.pow(&a.c0, &p));
+        assert_eq!(fp12.frobenius(&a), fp12.pow(&a, &p));
+
+        // Fp12 has degree 12 over Fp
+        assert_eq!(fp12.frobenius_map(&a, 12), a);
+        assert_ne!(fp12.frobenius_map(&a, 6), a);
+    }
+
+    #[test]
+    fn test_conjugation() {
+        let fp12 = bn254_tower();
+        let a = sample_fp12(&fp12, 7);
+
+        // a^(p^6) is the conjugate over Fp6
+        assert_eq!(fp12.frobenius_map(&a, 6), fp12.conjugate(&a));
+        // the norm a * conj(a) lies in Fp6
+        let norm = fp12.mul(&a, &fp12.conjugate(&a));
+        assert_eq!(norm.c1, fp12.base().zero());
+
+        let fp2 = fp12.base().base();
+        let x = a.c0.c0.clone();
+        assert_eq!(fp2.conjugate(&x), fp2.frobenius(&x));
+    }
+}
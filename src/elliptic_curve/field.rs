@@ -0,0 +1,100 @@
+use super::finite_field::FiniteField;
+use crate::error::Result;
+use num_bigint::BigUint;
+use std::fmt::Debug;
+
+// Arithmetic shared by the prime field and its extensions. The field object
+// carries the parameters (modulus, non-residue, Frobenius constants) and the
+// elements are plain values, in the same style as FiniteField's BigUint API.
+pub trait Field {
+    type Element: Clone + PartialEq + Debug;
+
+    // p, the characteristic of the underlying prime field
+    fn characteristic(&self) -> &BigUint;
+
+    fn zero(&self) -> Self::Element;
+
+    fn one(&self) -> Self::Element;
+
+    fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    fn sub(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    fn neg(&self, a: &Self::Element) -> Self::Element;
+
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    fn inv(&self, a: &Self::Element) -> Result<Self::Element>;
+
+    // a -> a^p
+    fn frobenius(&self, a: &Self::Element) -> Self::Element;
+
+    fn square(&self, a: &Self::Element) -> Self::Element {
+        self.mul(a, a)
+    }
+
+    fn double(&self, a: &Self::Element) -> Self::Element {
+        self.add(a, a)
+    }
+
+    fn is_zero(&self, a: &Self::Element) -> bool {
+        a == &self.zero()
+    }
+
+    fn pow(&self, a: &Self::Element, exponent: &BigUint) -> Self::Element {
+        let mut result = self.one();
+        for i in (0..exponent.bits()).rev() {
+            result = self.square(&result);
+            if exponent.bit(i) {
+                result = self.mul(&result, a);
+            }
+        }
+        result
+    }
+
+    // a -> a^(p^power)
+    fn frobenius_map(&self, a: &Self::Element, power: usize) -> Self::Element {
+        (0..power).fold(a.clone(), |acc, _| self.frobenius(&acc))
+    }
+}
+
+impl Field for FiniteField {
+    type Element = BigUint;
+
+    fn characteristic(&self) -> &BigUint {
+        self.p()
+    }
+
+    fn zero(&self) -> BigUint {
+        BigUint::from(0u32)
+    }
+
+    fn one(&self) -> BigUint {
+        BigUint::from(1u32) % self.p()
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        FiniteField::add(self, a, b)
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        FiniteField::sub(self, &(a % self.p()), &(b % self.p()))
+    }
+
+    fn neg(&self, a: &BigUint) -> BigUint {
+        (self.p() - a % self.p()) % self.p()
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        FiniteField::mul(self, a, b)
+    }
+
+    fn inv(&self, a: &BigUint) -> Result<BigUint> {
+        self.try_inv_mul(a)
+    }
+
+    // the identity on a prime field
+    fn frobenius(&self, a: &BigUint) -> BigUint {
+        a % self.p()
+    }
+}
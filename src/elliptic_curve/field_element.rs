@@ -0,0 +1,313 @@
+use super::finite_field::{FiniteField, Repr};
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// An element of a prime field that remembers which field it belongs to.
+// Arithmetic between elements of different fields is rejected.
+#[derive(Debug, Clone)]
+pub struct FieldElement<'a> {
+    // the value in the field's backend representation, e.g. Montgomery form
+    repr: Repr,
+    field: &'a FiniteField,
+}
+
+impl<'a> FieldElement<'a> {
+    // value is reduced modulo the field's modulus
+    pub fn new(value: &BigUint, field: &'a FiniteField) -> Self {
+        FieldElement {
+            repr: field.to_repr(value),
+            field,
+        }
+    }
+
+    // canonical residue in [0, p)
+    pub fn value(&self) -> BigUint {
+        self.field.to_canonical(&self.repr)
+    }
+
+    pub fn field(&self) -> &'a FiniteField {
+        self.field
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.field.repr_is_zero(&self.repr)
+    }
+
+    pub fn try_add(&self, rhs: &FieldElement<'a>) -> Result<FieldElement<'a>> {
+        self.check_same_field(rhs)?;
+        Ok(self.with_repr(self.field.repr_add(&self.repr, &rhs.repr)))
+    }
+
+    pub fn try_sub(&self, rhs: &FieldElement<'a>) -> Result<FieldElement<'a>> {
+        self.check_same_field(rhs)?;
+        Ok(self.with_repr(self.field.repr_sub(&self.repr, &rhs.repr)))
+    }
+
+    pub fn try_mul(&self, rhs: &FieldElement<'a>) -> Result<FieldElement<'a>> {
+        self.check_same_field(rhs)?;
+        Ok(self.with_repr(self.field.repr_mul(&self.repr, &rhs.repr)))
+    }
+
+    pub fn try_div(&self, rhs: &FieldElement<'a>) -> Result<FieldElement<'a>> {
+        self.try_mul(&rhs.try_inv()?)
+    }
+
+    pub fn try_inv(&self) -> Result<FieldElement<'a>> {
+        Ok(self.with_repr(self.field.repr_inv(&self.repr)?))
+    }
+
+    pub fn inv(&self) -> FieldElement<'a> {
+        self.try_inv().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // Montgomery's trick on field elements, used to normalize many
+    // projective points with a single inversion.
+    pub fn batch_inv(elements: &[FieldElement<'a>]) -> Result<Vec<FieldElement<'a>>> {
+        let Some(first) = elements.first() else {
+            return Ok(Vec::new());
+        };
+
+        let mut prefix = Vec::with_capacity(elements.len());
+        let mut acc = first.clone();
+        prefix.push(acc.clone());
+        for e in &elements[1..] {
+            acc = acc.try_mul(e)?;
+            prefix.push(acc.clone());
+        }
+
+        let mut inv = match acc.try_inv() {
+            Ok(inv) => inv,
+            Err(e) => return Err(elements.iter().find_map(|n| n.try_inv().err()).unwrap_or(e)),
+        };
+
+        let mut result = prefix.clone();
+        for i in (1..elements.len()).rev() {
+            result[i] = &inv * &prefix[i - 1];
+            inv = &inv * &elements[i];
+        }
+        result[0] = inv;
+        Ok(result)
+    }
+
+    pub fn square(&self) -> FieldElement<'a> {
+        self.with_repr(self.field.repr_mul(&self.repr, &self.repr))
+    }
+
+    pub fn pow(&self, exponent: &BigUint) -> FieldElement<'a> {
+        self.with_repr(self.field.repr_pow(&self.repr, exponent))
+    }
+
+    fn with_repr(&self, repr: Repr) -> FieldElement<'a> {
+        FieldElement {
+            repr,
+            field: self.field,
+        }
+    }
+
+    fn check_same_field(&self, rhs: &FieldElement<'a>) -> Result<()> {
+        if std::ptr::eq(self.field, rhs.field) || self.field == rhs.field {
+            return Ok(());
+        }
+        Err(EccError::FieldMismatch {
+            left: self.field.p().clone(),
+            right: rhs.field.p().clone(),
+        })
+    }
+}
+
+impl PartialEq for FieldElement<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.repr == other.repr && self.field == other.field
+    }
+}
+
+impl Eq for FieldElement<'_> {}
+
+impl Display for FieldElement<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (mod {})", self.value(), self.field.p())
+    }
+}
+
+impl<'a> Neg for &FieldElement<'a> {
+    type Output = FieldElement<'a>;
+
+    fn neg(self) -> FieldElement<'a> {
+        self.with_repr(self.field.repr_neg(&self.repr))
+    }
+}
+
+impl<'a> Neg for FieldElement<'a> {
+    type Output = FieldElement<'a>;
+
+    fn neg(self) -> FieldElement<'a> {
+        -&self
+    }
+}
+
+// Implements the operator for every combination of owned and borrowed operands,
+// plus the matching *Assign trait. Mixing fields panics, use try_* to handle it.
+macro_rules! impl_binary_op {
+    ($op:ident, $method:ident, $try_method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl<'a> $op<&FieldElement<'a>> for &FieldElement<'a> {
+            type Output = FieldElement<'a>;
+
+            fn $method(self, rhs: &FieldElement<'a>) -> FieldElement<'a> {
+                self.$try_method(rhs).unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+
+        impl<'a> $op<FieldElement<'a>> for &FieldElement<'a> {
+            type Output = FieldElement<'a>;
+
+            fn $method(self, rhs: FieldElement<'a>) -> FieldElement<'a> {
+                self.$method(&rhs)
+            }
+        }
+
+        impl<'a> $op<&FieldElement<'a>> for FieldElement<'a> {
+            type Output = FieldElement<'a>;
+
+            fn $method(self, rhs: &FieldElement<'a>) -> FieldElement<'a> {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl<'a> $op<FieldElement<'a>> for FieldElement<'a> {
+            type Output = FieldElement<'a>;
+
+            fn $method(self, rhs: FieldElement<'a>) -> FieldElement<'a> {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl<'a> $op_assign<&FieldElement<'a>> for FieldElement<'a> {
+            fn $method_assign(&mut self, rhs: &FieldElement<'a>) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+
+        impl<'a> $op_assign<FieldElement<'a>> for FieldElement<'a> {
+            fn $method_assign(&mut self, rhs: FieldElement<'a>) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, try_add, AddAssign, add_assign);
+impl_binary_op!(Sub, sub, try_sub, SubAssign, sub_assign);
+impl_binary_op!(Mul, mul, try_mul, MulAssign, mul_assign);
+impl_binary_op!(Div, div, try_div, DivAssign, div_assign);
+
+#[cfg(test)]
+mod fe_test {
+    use super::{BigUint, EccError, FieldElement, FiniteField};
+
+    #[test]
+    fn test_operators() {
+        let f = FiniteField::new(BigUint::from(11u32));
+        let a = f.element(&BigUint::from(4u32));
+        let b = f.element(&BigUint::from(10u32));
+
+        assert_eq!(&a + &b, f.element(&BigUint::from(3u32)));
+        assert_eq!(&a - &b, f.element(&BigUint::from(5u32)));
+        assert_eq!(&a * &b, f.element(&BigUint::from(7u32)));
+        assert_eq!(&a / &b, f.element(&BigUint::from(7u32)));
+        assert_eq!(-&a, f.element(&BigUint::from(7u32)));
+        assert_eq!(-f.zero(), f.zero());
+        assert_eq!(a.inv(), f.element(&BigUint::from(3u32)));
+        assert_eq!(a.square(), f.element(&BigUint::from(5u32)));
+        assert_eq!(a.pow(&BigUint::from(10u32)), f.one());
+    }
+
+    #[test]
+    fn test_assign_operators() {
+        let f = FiniteField::new(BigUint::from(11u32));
+        let b = f.element(&BigUint::from(10u32));
+
+        let mut a = f.element(&BigUint::from(4u32));
+        a += &b;
+        assert_eq!(a, f.element(&BigUint::from(3u32)));
+        a -= &b;
+        assert_eq!(a, f.element(&BigUint::from(4u32)));
+        a *= &b;
+        assert_eq!(a, f.element(&BigUint::from(7u32)));
+        a /= b;
+        assert_eq!(a, f.element(&BigUint::from(4u32)));
+    }
+
+    #[test]
+    fn test_reduces_on_creation() {
+        let f = FiniteField::new(BigUint::from(11u32));
+        assert_eq!(
+            f.element(&BigUint::from(15u32)).value(),
+            BigUint::from(4u32)
+        );
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        // 33 takes the Montgomery backend, 32 the plain one
+        for p in [32u32, 33] {
+            let f = FiniteField::new(BigUint::from(p));
+            assert_eq!(f.is_montgomery(), p % 2 == 1);
+            for a in 0..p {
+                for b in 0..p {
+                    let (x, y) = (f.element(&BigUint::from(a)), f.element(&BigUint::from(b)));
+                    assert_eq!((&x * &y).value(), BigUint::from(a * b % p));
+                    assert_eq!((&x + &y).value(), BigUint::from((a + b) % p));
+                    assert_eq!((&x - &y).value(), BigUint::from((a + p - b) % p));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_inv() {
+        let f = FiniteField::new(BigUint::from(17u32));
+        let elements: Vec<_> = (1..17u32).map(|a| f.element(&BigUint::from(a))).collect();
+        let inverses = FieldElement::batch_inv(&elements).expect("all elements are invertible");
+        for (a, inv) in elements.iter().zip(inverses.iter()) {
+            assert_eq!(a * inv, f.one());
+        }
+
+        let with_zero = vec![f.one(), f.zero()];
+        assert!(FieldElement::batch_inv(&with_zero).is_err());
+    }
+
+    #[test]
+    fn test_mixing_fields_is_rejected() {
+        let f = FiniteField::new(BigUint::from(11u32));
+        let g = FiniteField::new(BigUint::from(13u32));
+        let a = f.element(&BigUint::from(4u32));
+        let b = g.element(&BigUint::from(4u32));
+
+        assert_eq!(
+            a.try_add(&b),
+            Err(EccError::FieldMismatch {
+                left: BigUint::from(11u32),
+                right: BigUint::from(13u32)
+            })
+        );
+        assert!(a.try_mul(&b).is_err());
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mixing_fields_operator_panics() {
+        let f = FiniteField::new(BigUint::from(11u32));
+        let g = FiniteField::new(BigUint::from(13u32));
+        let _ = f.element(&BigUint::from(4u32)) * g.element(&BigUint::from(4u32));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_division_by_zero_panics() {
+        let f = FiniteField::new(BigUint::from(11u32));
+        let _ = f.one() / f.zero();
+    }
+}
@@ -1,50 +1,404 @@
-use num_bigint::BigUint;
+use super::field_element::FieldElement;
+use super::montgomery_form::MontgomeryForm;
+use super::u256::{SpecialPrime, U256};
+use crate::error::{EccError, Result};
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::{One, Zero};
 
+#[derive(Debug, Clone)]
 pub struct FiniteField {
-    pub p: BigUint,
+    p: BigUint,
+    backend: Backend,
 }
 
+// How FieldElement values are represented internally.
+// The BigUint methods on FiniteField always take and return canonical residues.
+#[derive(Debug, Clone)]
+enum Backend {
+    // canonical residues in [0, p)
+    Plain,
+    // a * R mod p, picked for every other odd modulus
+    Montgomery(MontgomeryForm),
+    // fixed-width limbs with a dedicated reduction, picked for secp256k1 and P-256
+    Special(SpecialPrime),
+}
+
+// A field element value in the representation chosen by its field's backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Repr {
+    Big(BigUint),
+    Limbs(U256),
+}
+
+impl PartialEq for FiniteField {
+    fn eq(&self, other: &Self) -> bool {
+        self.p == other.p
+    }
+}
+
+impl Eq for FiniteField {}
+
 impl FiniteField {
+    pub fn new(p: BigUint) -> Self {
+        let backend = if let Some(prime) = SpecialPrime::detect(&p) {
+            Backend::Special(prime)
+        } else if let Some(form) = MontgomeryForm::new(&p) {
+            Backend::Montgomery(form)
+        } else {
+            Backend::Plain
+        };
+        FiniteField { p, backend }
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    pub fn is_montgomery(&self) -> bool {
+        matches!(self.backend, Backend::Montgomery(_))
+    }
+
+    pub fn special_prime(&self) -> Option<SpecialPrime> {
+        match self.backend {
+            Backend::Special(prime) => Some(prime),
+            _ => None,
+        }
+    }
+
+    pub fn element(&self, value: &BigUint) -> FieldElement<'_> {
+        FieldElement::new(value, self)
+    }
+
+    pub fn zero(&self) -> FieldElement<'_> {
+        self.element(&BigUint::from(0u32))
+    }
+
+    pub fn one(&self) -> FieldElement<'_> {
+        self.element(&BigUint::from(1u32))
+    }
+
     pub fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
-        let sum = a + b;
-        sum.modpow(&BigUint::from(1u32), &self.p)
+        (a + b) % &self.p
     }
     pub fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
-        let product = a * b;
-        product.modpow(&BigUint::from(1u32), &self.p)
+        (a * b) % &self.p
     }
 
     pub fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
-        self.add(a, &self.inv_add(b))
+        self.try_sub(a, b).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_sub(&self, a: &BigUint, b: &BigUint) -> Result<BigUint> {
+        Ok(self.add(a, &self.try_inv_add(b)?))
     }
 
     pub fn div(&self, a: &BigUint, b: &BigUint) -> BigUint {
-        self.mul(a, &self.inv_mul(b))
+        self.try_div(a, b).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_div(&self, a: &BigUint, b: &BigUint) -> Result<BigUint> {
+        Ok(self.mul(a, &self.try_inv_mul(b)?))
     }
 
     pub fn inv_add(&self, n: &BigUint) -> BigUint {
-        assert!(
-            n < &self.p,
-            "number: {} is bigger or equal than modulus {}",
-            n,
-            &self.p
-        );
-        &self.p - n
+        self.try_inv_add(n).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_inv_add(&self, n: &BigUint) -> Result<BigUint> {
+        if n >= &self.p {
+            return Err(EccError::ScalarOutOfRange {
+                value: n.clone(),
+                modulus: self.p.clone(),
+            });
+        }
+        Ok(&self.p - n)
     }
 
     pub fn inv_mul(&self, n: &BigUint) -> BigUint {
-        n.modpow(&(&self.p - BigUint::from(2u32)), &self.p)
+        self.try_inv_mul(n).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // Extended Euclidean algorithm, so it also works for composite moduli.
+    // Fails when gcd(n, p) != 1, which includes n = 0.
+    pub fn try_inv_mul(&self, n: &BigUint) -> Result<BigUint> {
+        let modulus = BigInt::from(self.p.clone());
+        // invariant: old_s * n = old_r (mod p)
+        let (mut old_r, mut r) = (BigInt::from(n % &self.p), modulus.clone());
+        let (mut old_s, mut s) = (BigInt::from(1), BigInt::from(0));
+        while !r.is_zero() {
+            let q = &old_r / &r;
+            let next_r = &old_r - &q * &r;
+            old_r = std::mem::replace(&mut r, next_r);
+            let next_s = &old_s - &q * &s;
+            old_s = std::mem::replace(&mut s, next_s);
+        }
+
+        if !old_r.is_one() {
+            return Err(EccError::NonInvertible {
+                value: n.clone(),
+                modulus: self.p.clone(),
+            });
+        }
+        Ok(old_s
+            .mod_floor(&modulus)
+            .to_biguint()
+            .expect("mod_floor of a positive modulus is non-negative"))
+    }
+
+    // Montgomery's trick: inverts every element with a single inversion
+    // and 3(N - 1) multiplications.
+    pub fn batch_inv(&self, elements: &[BigUint]) -> Result<Vec<BigUint>> {
+        if elements.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // prefix[i] = elements[0] * ... * elements[i]
+        let mut prefix = Vec::with_capacity(elements.len());
+        let mut acc = BigUint::from(1u32);
+        for e in elements {
+            acc = self.mul(&acc, e);
+            prefix.push(acc.clone());
+        }
+
+        let mut inv = match self.try_inv_mul(&acc) {
+            Ok(inv) => inv,
+            // report the element that is not invertible rather than the product
+            Err(e) => {
+                return Err(elements
+                    .iter()
+                    .find_map(|n| self.try_inv_mul(n).err())
+                    .unwrap_or(e))
+            }
+        };
+
+        let mut result = vec![BigUint::from(0u32); elements.len()];
+        for i in (1..elements.len()).rev() {
+            // inv = (e0 * ... * ei)^(-1)
+            result[i] = self.mul(&inv, &prefix[i - 1]);
+            inv = self.mul(&inv, &elements[i]);
+        }
+        result[0] = inv;
+        Ok(result)
+    }
+
+    // Legendre symbol (a / p) for an odd prime p: 1 for non-zero squares,
+    // -1 for non-squares and 0 when p divides a.
+    pub fn legendre(&self, a: &BigUint) -> i8 {
+        let a = a % &self.p;
+        if a == BigUint::from(0u32) {
+            return 0;
+        }
+        // Euler's criterion: a^((p - 1) / 2) = +-1
+        let exponent = (&self.p - BigUint::from(1u32)) >> 1;
+        if a.modpow(&exponent, &self.p) == BigUint::from(1u32) {
+            1
+        } else {
+            -1
+        }
+    }
+
+    // zero counts as a square
+    pub fn is_square(&self, a: &BigUint) -> bool {
+        self.legendre(a) >= 0
+    }
+
+    // Both square roots of a, smaller one first, or None when a is not a square.
+    // Meant for an odd prime p. Other moduli can fool Euler's criterion, so
+    // the root is checked and None returned when it is wrong.
+    pub fn sqrt(&self, a: &BigUint) -> Option<(BigUint, BigUint)> {
+        let a = a % &self.p;
+        match self.legendre(&a) {
+            0 => return Some((a.clone(), a)),
+            -1 => return None,
+            _ => {}
+        }
+
+        // the low bits of an odd p give p mod 4 and p mod 8
+        let root = if self.p.bit(1) {
+            self.sqrt_3_mod_4(&a)
+        } else if self.p.bit(2) {
+            self.sqrt_5_mod_8(&a)
+        } else {
+            self.sqrt_tonelli_shanks(&a)?
+        };
+        if self.mul(&root, &root) != a {
+            return None;
+        }
+
+        let other = &self.p - &root;
+        if root <= other {
+            Some((root, other))
+        } else {
+            Some((other, root))
+        }
+    }
+
+    // r = a^((p + 1) / 4)
+    fn sqrt_3_mod_4(&self, a: &BigUint) -> BigUint {
+        let exponent = (&self.p + BigUint::from(1u32)) >> 2;
+        a.modpow(&exponent, &self.p)
+    }
+
+    // Atkin's algorithm: b = (2a)^((p - 5) / 8), i = 2ab^2, r = ab(i - 1)
+    fn sqrt_5_mod_8(&self, a: &BigUint) -> BigUint {
+        let two_a = self.mul(&BigUint::from(2u32), a);
+        let exponent = (&self.p - BigUint::from(5u32)) >> 3;
+        let b = two_a.modpow(&exponent, &self.p);
+        let i = self.mul(&two_a, &self.mul(&b, &b));
+        self.mul(&self.mul(a, &b), &self.sub(&i, &BigUint::from(1u32)))
+    }
+
+    // None if a step that cannot fail for a prime p does: no non-square z
+    // below p, or no i < m with t^(2^i) = 1.
+    fn sqrt_tonelli_shanks(&self, a: &BigUint) -> Option<BigUint> {
+        let one = BigUint::from(1u32);
+
+        // p - 1 = q * 2^s, q odd
+        let p_minus_one = &self.p - &one;
+        let s = p_minus_one
+            .trailing_zeros()
+            .expect("p - 1 is non-zero for an odd prime");
+        let q = &p_minus_one >> s;
+
+        // any non-square z
+        let mut z = BigUint::from(2u32);
+        while self.legendre(&z) != -1 {
+            z += 1u32;
+            if z >= self.p {
+                return None;
+            }
+        }
+
+        let mut m = s;
+        let mut c = z.modpow(&q, &self.p);
+        let mut t = a.modpow(&q, &self.p);
+        let mut r = a.modpow(&((&q + &one) >> 1), &self.p);
+
+        while t != one {
+            // least i with t^(2^i) = 1
+            let mut i = 0;
+            let mut t_pow = t.clone();
+            while t_pow != one {
+                if i + 1 >= m {
+                    return None;
+                }
+                t_pow = self.mul(&t_pow, &t_pow);
+                i += 1;
+            }
+
+            let b = c.modpow(&(&one << (m - i - 1)), &self.p);
+            m = i;
+            c = self.mul(&b, &b);
+            t = self.mul(&t, &c);
+            r = self.mul(&r, &b);
+        }
+        Some(r)
+    }
+
+    // Conversions between canonical residues and the backend representation,
+    // done once at the FieldElement boundary.
+    pub(crate) fn to_repr(&self, a: &BigUint) -> Repr {
+        match &self.backend {
+            Backend::Plain => Repr::Big(a % &self.p),
+            Backend::Montgomery(form) => Repr::Big(form.to_montgomery(a)),
+            Backend::Special(_) => {
+                Repr::Limbs(U256::from_biguint(&(a % &self.p)).expect("residue fits in 256 bits"))
+            }
+        }
+    }
+
+    pub(crate) fn to_canonical(&self, a: &Repr) -> BigUint {
+        match (&self.backend, a) {
+            (Backend::Plain, Repr::Big(a)) => a.clone(),
+            (Backend::Montgomery(form), Repr::Big(a)) => form.from_montgomery(a),
+            (Backend::Special(_), Repr::Limbs(a)) => a.to_biguint(),
+            _ => unreachable!("representation does not match the field backend"),
+        }
+    }
+
+    pub(crate) fn repr_is_zero(&self, a: &Repr) -> bool {
+        match a {
+            Repr::Big(a) => a == &BigUint::from(0u32),
+            Repr::Limbs(a) => a.is_zero(),
+        }
+    }
+
+    // Addition and subtraction are the same for plain and Montgomery residues.
+    pub(crate) fn repr_add(&self, a: &Repr, b: &Repr) -> Repr {
+        match (&self.backend, a, b) {
+            (Backend::Special(prime), Repr::Limbs(a), Repr::Limbs(b)) => {
+                Repr::Limbs(prime.add(a, b))
+            }
+            (_, Repr::Big(a), Repr::Big(b)) => {
+                let sum = a + b;
+                if sum >= self.p {
+                    Repr::Big(sum - &self.p)
+                } else {
+                    Repr::Big(sum)
+                }
+            }
+            _ => unreachable!("representation does not match the field backend"),
+        }
+    }
+
+    pub(crate) fn repr_sub(&self, a: &Repr, b: &Repr) -> Repr {
+        match (&self.backend, a, b) {
+            (Backend::Special(prime), Repr::Limbs(a), Repr::Limbs(b)) => {
+                Repr::Limbs(prime.sub(a, b))
+            }
+            (_, Repr::Big(a), Repr::Big(b)) => {
+                if a >= b {
+                    Repr::Big(a - b)
+                } else {
+                    Repr::Big(a + &self.p - b)
+                }
+            }
+            _ => unreachable!("representation does not match the field backend"),
+        }
+    }
+
+    pub(crate) fn repr_neg(&self, a: &Repr) -> Repr {
+        match (&self.backend, a) {
+            (Backend::Special(prime), Repr::Limbs(a)) => Repr::Limbs(prime.neg(a)),
+            (_, Repr::Big(a)) if a == &BigUint::from(0u32) => Repr::Big(a.clone()),
+            (_, Repr::Big(a)) => Repr::Big(&self.p - a),
+            _ => unreachable!("representation does not match the field backend"),
+        }
+    }
+
+    pub(crate) fn repr_mul(&self, a: &Repr, b: &Repr) -> Repr {
+        match (&self.backend, a, b) {
+            (Backend::Plain, Repr::Big(a), Repr::Big(b)) => Repr::Big(self.mul(a, b)),
+            (Backend::Montgomery(form), Repr::Big(a), Repr::Big(b)) => Repr::Big(form.mul(a, b)),
+            (Backend::Special(prime), Repr::Limbs(a), Repr::Limbs(b)) => {
+                Repr::Limbs(prime.mul(a, b))
+            }
+            _ => unreachable!("representation does not match the field backend"),
+        }
+    }
+
+    pub(crate) fn repr_pow(&self, a: &Repr, exponent: &BigUint) -> Repr {
+        match (&self.backend, a) {
+            (Backend::Plain, Repr::Big(a)) => Repr::Big(a.modpow(exponent, &self.p)),
+            (Backend::Montgomery(form), Repr::Big(a)) => Repr::Big(form.pow(a, exponent)),
+            (Backend::Special(prime), Repr::Limbs(a)) => Repr::Limbs(prime.pow(a, exponent)),
+            _ => unreachable!("representation does not match the field backend"),
+        }
+    }
+
+    pub(crate) fn repr_inv(&self, a: &Repr) -> Result<Repr> {
+        Ok(self.to_repr(&self.try_inv_mul(&self.to_canonical(a))?))
     }
 }
 
+#[cfg(test)]
 mod ff_test {
-    use super::{BigUint, FiniteField};
+    use super::{BigUint, EccError, FiniteField, SpecialPrime};
 
     #[test]
     fn test_add() {
-        let f = FiniteField {
-            p: BigUint::from(11u32),
-        };
+        let f = FiniteField::new(BigUint::from(11u32));
         let a = BigUint::from(4u32);
         let b = BigUint::from(10u32);
         let sum = f.add(&a, &b);
@@ -54,9 +408,7 @@ mod ff_test {
 
     #[test]
     fn test_add_2() {
-        let f = FiniteField {
-            p: BigUint::from(32u32),
-        };
+        let f = FiniteField::new(BigUint::from(32u32));
         let a = BigUint::from(4u32);
         let b = BigUint::from(10u32);
         let sum = f.add(&a, &b);
@@ -66,9 +418,7 @@ mod ff_test {
 
     #[test]
     fn test_mul() {
-        let f = FiniteField {
-            p: BigUint::from(11u32),
-        };
+        let f = FiniteField::new(BigUint::from(11u32));
         let a = BigUint::from(4u32);
         let b = BigUint::from(10u32);
         let prod = f.mul(&a, &b);
@@ -78,9 +428,7 @@ mod ff_test {
 
     #[test]
     fn test_inv_add() {
-        let f = FiniteField {
-            p: BigUint::from(51u32),
-        };
+        let f = FiniteField::new(BigUint::from(51u32));
         let a = BigUint::from(4u32);
         let prod = f.inv_add(&a);
 
@@ -90,18 +438,43 @@ mod ff_test {
     #[test]
     #[should_panic]
     fn test_inv_add_2() {
-        let f = FiniteField {
-            p: BigUint::from(51u32),
-        };
+        let f = FiniteField::new(BigUint::from(51u32));
         let a = BigUint::from(52u32);
         let _ = f.inv_add(&a);
     }
 
+    #[test]
+    fn test_try_inv_add_out_of_range() {
+        let f = FiniteField::new(BigUint::from(51u32));
+        let a = BigUint::from(52u32);
+        assert_eq!(
+            f.try_inv_add(&a),
+            Err(EccError::ScalarOutOfRange {
+                value: a,
+                modulus: BigUint::from(51u32)
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_inv_mul_zero() {
+        let f = FiniteField::new(BigUint::from(11u32));
+        let a = BigUint::from(22u32);
+        assert_eq!(
+            f.try_inv_mul(&a),
+            Err(EccError::NonInvertible {
+                value: a,
+                modulus: BigUint::from(11u32)
+            })
+        );
+        assert!(f
+            .try_div(&BigUint::from(3u32), &BigUint::from(0u32))
+            .is_err());
+    }
+
     #[test]
     fn test_inv_mul() {
-        let f = FiniteField {
-            p: BigUint::from(11u32),
-        };
+        let f = FiniteField::new(BigUint::from(11u32));
         let a = BigUint::from(4u32);
         let prod = f.inv_mul(&a);
         assert_eq!(prod, BigUint::from(3u32));
@@ -109,9 +482,7 @@ mod ff_test {
 
     #[test]
     fn test_sub() {
-        let f = FiniteField {
-            p: BigUint::from(11u32),
-        };
+        let f = FiniteField::new(BigUint::from(11u32));
         let a = BigUint::from(10u32);
         let b = BigUint::from(4u32);
         let prod = f.sub(&a, &b);
@@ -120,20 +491,190 @@ mod ff_test {
 
     #[test]
     fn test_sub_1() {
-        let f = FiniteField {
-            p: BigUint::from(11u32),
-        };
+        let f = FiniteField::new(BigUint::from(11u32));
         let a = BigUint::from(4u32);
         let b = BigUint::from(10u32);
         let prod = f.sub(&a, &b);
         assert_eq!(prod, BigUint::from(5u32));
     }
 
+    #[test]
+    fn test_backend_selection() {
+        let secp256k1_p = BigUint::parse_bytes(
+            b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .expect("could not convert str to p");
+        let f = FiniteField::new(secp256k1_p.clone());
+        assert_eq!(f.special_prime(), Some(SpecialPrime::Secp256k1));
+        assert!(!f.is_montgomery());
+
+        // field elements give the same results whatever the backend
+        let a = BigUint::parse_bytes(
+            b"79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            16,
+        )
+        .expect("could not convert str to a");
+        let b = &secp256k1_p - BigUint::from(5u32);
+        let (x, y) = (f.element(&a), f.element(&b));
+        assert_eq!((&x * &y).value(), f.mul(&a, &b));
+        assert_eq!((&x + &y).value(), f.add(&a, &b));
+        assert_eq!((&x - &y).value(), f.sub(&a, &b));
+        assert_eq!((&x / &y).value(), f.div(&a, &b));
+
+        assert!(FiniteField::new(BigUint::from(11u32)).is_montgomery());
+        assert_eq!(FiniteField::new(BigUint::from(11u32)).special_prime(), None);
+        assert!(!FiniteField::new(BigUint::from(32u32)).is_montgomery());
+    }
+
+    #[test]
+    fn test_sqrt_toy_field() {
+        // 17 = 1 (mod 8) goes through Tonelli-Shanks
+        let f = FiniteField::new(BigUint::from(17u32));
+        let squares: Vec<u32> = (1..17u32)
+            .filter(|a| f.is_square(&BigUint::from(*a)))
+            .collect();
+        assert_eq!(squares, vec![1, 2, 4, 8, 9, 13, 15, 16]);
+
+        assert_eq!(f.legendre(&BigUint::from(0u32)), 0);
+        assert_eq!(f.legendre(&BigUint::from(2u32)), 1);
+        assert_eq!(f.legendre(&BigUint::from(3u32)), -1);
+
+        // y^2 = 2^3 + 2 * 2 + 2 = 14 is not a square, so x = 2 is not on y^2 = x^3 + 2x + 2
+        assert_eq!(f.sqrt(&BigUint::from(14u32)), None);
+        // x = 5: x^3 + 2x + 2 = 1 (mod 17), y = 1 or 16
+        assert_eq!(
+            f.sqrt(&BigUint::from(1u32)),
+            Some((BigUint::from(1u32), BigUint::from(16u32)))
+        );
+        assert_eq!(
+            f.sqrt(&BigUint::from(2u32)),
+            Some((BigUint::from(6u32), BigUint::from(11u32)))
+        );
+        assert_eq!(
+            f.sqrt(&BigUint::from(0u32)),
+            Some((BigUint::from(0u32), BigUint::from(0u32)))
+        );
+    }
+
+    #[test]
+    fn test_sqrt_all_paths() {
+        // 13 = 5 (mod 8), 19 = 3 (mod 4), 41 and 97 = 1 (mod 8)
+        for p in [13u32, 19, 29, 41, 97, 103] {
+            let f = FiniteField::new(BigUint::from(p));
+            for a in 0..p {
+                let a = BigUint::from(a);
+                match f.sqrt(&a) {
+                    Some((r1, r2)) => {
+                        assert!(f.is_square(&a));
+                        assert_eq!(f.mul(&r1, &r1), a);
+                        assert_eq!(f.mul(&r2, &r2), a);
+                        assert_eq!(f.add(&r1, &r2), BigUint::from(0u32));
+                    }
+                    None => assert!(!f.is_square(&a)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_composite_modulus() {
+        // 9, 33 and 65 = 1 (mod 8) reach Tonelli-Shanks, 15 and 21 the
+        // other two paths; every call must end with None or a real root
+        for p in [8u32, 9, 15, 21, 33, 65] {
+            let f = FiniteField::new(BigUint::from(p));
+            for a in 0..p {
+                let a = BigUint::from(a);
+                if let Some((r1, r2)) = f.sqrt(&a) {
+                    assert_eq!(f.mul(&r1, &r1), a);
+                    assert_eq!(f.mul(&r2, &r2), a);
+                }
+            }
+        }
+        // -1 passes Euler's criterion mod 9, and Tonelli-Shanks finds no
+        // i < m with t^(2^i) = 1
+        let f = FiniteField::new(BigUint::from(9u32));
+        assert_eq!(f.legendre(&BigUint::from(8u32)), 1);
+        assert_eq!(f.sqrt(&BigUint::from(8u32)), None);
+    }
+
+    #[test]
+    fn test_sqrt_secp256k1() {
+        let p = BigUint::parse_bytes(
+            b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .expect("could not convert str to p");
+        let gx = BigUint::parse_bytes(
+            b"79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            16,
+        )
+        .expect("could not convert str to gx");
+        let gy = BigUint::parse_bytes(
+            b"483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+            16,
+        )
+        .expect("could not convert str to gy");
+        let f = FiniteField::new(p.clone());
+
+        // recover G's y from x: y^2 = x^3 + 7
+        let rhs = f.add(&f.mul(&f.mul(&gx, &gx), &gx), &BigUint::from(7u32));
+        let (r1, r2) = f.sqrt(&rhs).expect("G is on the curve");
+        assert!(r1 == gy || r2 == gy);
+
+        // -1 is not a square when p = 3 (mod 4)
+        assert_eq!(f.sqrt(&(&p - BigUint::from(1u32))), None);
+    }
+
+    #[test]
+    fn test_inv_mul_composite_modulus() {
+        let f = FiniteField::new(BigUint::from(32u32));
+        for a in (1..32u32).step_by(2) {
+            let inv = f.inv_mul(&BigUint::from(a));
+            assert_eq!(f.mul(&BigUint::from(a), &inv), BigUint::from(1u32));
+        }
+        for a in (0..32u32).step_by(2) {
+            assert_eq!(
+                f.try_inv_mul(&BigUint::from(a)),
+                Err(EccError::NonInvertible {
+                    value: BigUint::from(a),
+                    modulus: BigUint::from(32u32)
+                })
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inv_mul_zero_panics() {
+        let f = FiniteField::new(BigUint::from(11u32));
+        let _ = f.inv_mul(&BigUint::from(0u32));
+    }
+
+    #[test]
+    fn test_batch_inv() {
+        let f = FiniteField::new(BigUint::from(11u32));
+        let elements: Vec<BigUint> = (1..11u32).map(BigUint::from).collect();
+        let inverses = f.batch_inv(&elements).expect("all elements are invertible");
+        for (a, inv) in elements.iter().zip(inverses.iter()) {
+            assert_eq!(inv, &f.inv_mul(a));
+        }
+        assert_eq!(f.batch_inv(&[]), Ok(vec![]));
+
+        let f = FiniteField::new(BigUint::from(32u32));
+        let elements: Vec<BigUint> = [3u32, 5, 6, 7].into_iter().map(BigUint::from).collect();
+        assert_eq!(
+            f.batch_inv(&elements),
+            Err(EccError::NonInvertible {
+                value: BigUint::from(6u32),
+                modulus: BigUint::from(32u32)
+            })
+        );
+    }
+
     #[test]
     fn tes_div() {
-        let f = FiniteField {
-            p: BigUint::from(11u32),
-        };
+        let f = FiniteField::new(BigUint::from(11u32));
         let a = BigUint::from(4u32);
         let b = BigUint::from(10u32);
         let prod = f.div(&a, &b);
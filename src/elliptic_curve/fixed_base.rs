@@ -0,0 +1,282 @@
+use super::curve_group::check_ladder_bits;
+use super::elliptic_curve::{EllipticCurve, Point};
+use super::jacobian::JacobianPoint;
+use super::projective::ProjectivePoint;
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+
+// window width of the generator tables built by ECDSA
+pub const FIXED_BASE_WINDOW: usize = 4;
+
+// Precomputed multiples of one base point g: rows[i][j] = j * 2^(w i) * g.
+// k * g is then the sum of one entry per w-bit window of k, with no
+// doublings at all. Row entry 0 is the identity. Only EllipticCurve fills
+// the rows; other curves keep just the base and bit length for the ladder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedBaseTable {
+    base: Point,
+    window: usize,
+    bits: u64,
+    rows: Vec<Vec<Point>>,
+}
+
+impl FixedBaseTable {
+    // `bits` is rounded up to whole windows
+    pub(crate) fn new(base: Point, window: usize, bits: u64, rows: Vec<Vec<Point>>) -> Self {
+        let bits = bits.div_ceil(window as u64) * window as u64;
+        FixedBaseTable {
+            base,
+            window,
+            bits,
+            rows,
+        }
+    }
+
+    // g, the point the table holds multiples of
+    pub fn base(&self) -> &Point {
+        &self.base
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    pub fn rows(&self) -> &[Vec<Point>] {
+        &self.rows
+    }
+
+    // scalars up to this many bits can be multiplied with the table
+    pub fn max_bits(&self) -> u64 {
+        self.bits
+    }
+
+    // the w-bit digit of k used with row i
+    pub(crate) fn digit(&self, k: &BigUint, row: usize) -> usize {
+        let start = (row * self.window) as u64;
+        (0..self.window as u64).fold(0, |acc, b| acc | ((k.bit(start + b) as usize) << b))
+    }
+
+    pub(crate) fn check_scalar(&self, k: &BigUint) -> Result<()> {
+        check_ladder_bits(k, self.max_bits())
+    }
+}
+
+// a table has 2^w entries per row, so wide windows get large quickly
+pub(crate) fn check_window(w: usize) -> Result<()> {
+    if !(1..=8).contains(&w) {
+        return Err(EccError::InvalidWindow {
+            width: w,
+            min: 1,
+            max: 8,
+        });
+    }
+    Ok(())
+}
+
+// Point as (x, y, z) with z = 0 for the identity, so it can be selected
+// coordinate-wise together with ordinary points.
+fn projective_coordinates(c: &Point) -> (BigUint, BigUint, BigUint) {
+    match c {
+        Point::Coordinates(x, y) => (x.clone(), y.clone(), BigUint::from(1u32)),
+        Point::Identity => (
+            BigUint::from(0u32),
+            BigUint::from(1u32),
+            BigUint::from(0u32),
+        ),
+    }
+}
+
+// Reads row[digit] by touching every entry and keeping one through a mask,
+// so the memory access pattern does not depend on digit.
+fn select(row: &[Point], digit: usize, bits: u64) -> (BigUint, BigUint, BigUint) {
+    let zero = BigUint::from(0u32);
+    let (mut x, mut y, mut z) = (zero.clone(), zero.clone(), zero);
+    for (j, entry) in row.iter().enumerate() {
+        // 1 if j == digit, else 0, without a comparison branch
+        let eq = ((j ^ digit) as u64).wrapping_sub(1) >> 63;
+        let mask = (BigUint::from(eq) << bits) - eq;
+        let (ex, ey, ez) = projective_coordinates(entry);
+        x |= ex & &mask;
+        y |= ey & &mask;
+        z |= ez & &mask;
+    }
+    (x, y, z)
+}
+
+impl EllipticCurve {
+    // Table for k * g with k of up to `bits` bits. Built in Jacobian
+    // coordinates and normalized with a single inversion.
+    pub fn fixed_base_table(&self, g: &Point, bits: u64, w: usize) -> Result<FixedBaseTable> {
+        check_window(w)?;
+        if !self.is_on_curve(g) {
+            return Err(EccError::NotOnCurve(g.clone()));
+        }
+
+        let row_count = bits.div_ceil(w as u64) as usize;
+        let mut points: Vec<JacobianPoint> = Vec::with_capacity(row_count << w);
+        let mut base = self.to_jacobian(g);
+        for _ in 0..row_count {
+            let mut entry = self.jacobian_identity();
+            for _ in 0..1usize << w {
+                points.push(entry.clone());
+                entry = self.jacobian_add(&entry, &base);
+            }
+            // entry is now 2^w * base
+            base = entry;
+        }
+
+        let affine = self.batch_to_affine(&points)?;
+        let rows = affine.chunks(1 << w).map(|row| row.to_vec()).collect();
+        Ok(FixedBaseTable::new(g.clone(), w, bits, rows))
+    }
+
+    pub fn fixed_base_mul(&self, table: &FixedBaseTable, k: &BigUint) -> Point {
+        self.try_fixed_base_mul(table, k)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // One masked table read and one complete addition per window, for every
+    // window of the table, so the operation sequence depends only on the
+    // table size. Suitable for secret scalars. A table built for another
+    // curve is rejected, and a rowless one from the CurveGroup fallback
+    // runs the padded ladder on its base.
+    pub fn try_fixed_base_mul(&self, table: &FixedBaseTable, k: &BigUint) -> Result<Point> {
+        table.check_scalar(k)?;
+        if !self.is_on_curve(table.base()) {
+            return Err(EccError::NotOnCurve(table.base().clone()));
+        }
+        if table.rows().is_empty() {
+            return self.try_ladder_scalar_mul(table.base(), k, table.max_bits());
+        }
+        let w = table.window();
+        if (table.rows().len() * w) as u64 != table.max_bits()
+            || table.rows().iter().any(|row| row.len() != 1 << w)
+        {
+            return Err(EccError::InvalidWindow {
+                width: w,
+                min: 1,
+                max: 8,
+            });
+        }
+
+        let f = self.field();
+        let bits = self.p().bits();
+        let mut acc = self.projective_identity();
+        for (i, row) in table.rows().iter().enumerate() {
+            let (x, y, z) = select(row, table.digit(k, i), bits);
+            let entry = ProjectivePoint::new(f.element(&x), f.element(&y), f.element(&z));
+            acc = self.complete_add(&acc, &entry);
+        }
+        self.try_projective_to_affine(&acc)
+    }
+}
+
+#[cfg(test)]
+mod fixed_base_test {
+    use super::{BigUint, EccError, EllipticCurve, FixedBaseTable, Point};
+    use crate::curves;
+    use crate::elliptic_curve::CurveGroup;
+
+    #[test]
+    fn test_matches_scalar_mul() {
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+        let g = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
+        for w in 1..=4 {
+            let table = ec
+                .fixed_base_table(&g, 5, w)
+                .expect("could not build table");
+            for k in 0..32u32 {
+                let k = BigUint::from(k);
+                assert_eq!(ec.fixed_base_mul(&table, &k), ec.scalar_mul(&g, &k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let params = curves::secp256k1();
+        let (ec, g) = (params.curve(), params.generator());
+        let table = ec
+            .fixed_base_table(g, 256, 4)
+            .expect("could not build table");
+        assert_eq!(table.rows().len(), 64);
+
+        let k = BigUint::parse_bytes(
+            b"AA5E28D6A97A2479A65527F7290311A3624D4CC0FA1578598EE3C2613BF99522",
+            16,
+        )
+        .expect("could not convert str to k");
+        assert_eq!(ec.fixed_base_mul(&table, &k), ec.scalar_mul(g, &k));
+        let n_minus_one = params.order() - BigUint::from(1u32);
+        assert_eq!(ec.fixed_base_mul(&table, &n_minus_one), ec.negate(g));
+
+        assert_eq!(
+            ec.try_fixed_base_mul(&table, &(BigUint::from(1u32) << 256)),
+            Err(EccError::ScalarOutOfRange {
+                value: BigUint::from(1u32) << 256,
+                modulus: BigUint::from(1u32) << 256
+            })
+        );
+    }
+
+    #[test]
+    fn test_foreign_table() {
+        let k = BigUint::from(12345u32);
+        let secp256k1 = curves::secp256k1();
+        let p256 = curves::secp256r1();
+        let g = secp256k1.generator();
+        let table = secp256k1
+            .curve()
+            .fixed_base_table(g, 256, 4)
+            .expect("could not build table");
+        assert_eq!(
+            p256.curve().try_fixed_base_mul(&table, &k),
+            Err(EccError::NotOnCurve(g.clone()))
+        );
+
+        // a table without rows, as the CurveGroup fallback builds them
+        let (ec, g) = (secp256k1.curve(), secp256k1.generator());
+        let rowless = FixedBaseTable::new(g.clone(), 4, 256, Vec::new());
+        assert_eq!(
+            ec.try_fixed_base_mul(&rowless, &k),
+            Ok(ec.scalar_mul(g, &k))
+        );
+
+        // rows that do not cover max_bits()
+        let short = FixedBaseTable::new(g.clone(), 4, 256, table.rows()[..8].to_vec());
+        assert_eq!(
+            ec.try_fixed_base_mul(&short, &k),
+            Err(EccError::InvalidWindow {
+                width: 4,
+                min: 1,
+                max: 8
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_window() {
+        let params = curves::secp256k1();
+        let (ec, g) = (params.curve(), params.generator());
+        let edwards = curves::edwards25519();
+        for w in [0, 9] {
+            let err = Err(EccError::InvalidWindow {
+                width: w,
+                min: 1,
+                max: 8,
+            });
+            assert_eq!(ec.fixed_base_table(g, 256, w), err);
+            // the affine fallback of CurveGroup
+            assert_eq!(
+                edwards
+                    .curve()
+                    .fixed_base_table(edwards.generator(), 253, w),
+                err
+            );
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use super::elliptic_curve::{EllipticCurve, Point};
+use super::wnaf::wnaf;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+
+// An endomorphism phi(x, y) = (beta x, y) of a curve with a = 0, where beta
+// is a cube root of unity mod p. On the subgroup of order n it acts as
+// multiplication by lambda, a cube root of unity mod n. v1 and v2 are a
+// short basis of the lattice {(x, y) : x + y lambda = 0 (mod n)}, so
+// n = |det(v1, v2)|.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlvEndomorphism {
+    beta: BigUint,
+    lambda: BigUint,
+    v1: (BigInt, BigInt),
+    v2: (BigInt, BigInt),
+    n: BigUint,
+}
+
+impl GlvEndomorphism {
+    pub fn new(beta: BigUint, lambda: BigUint, v1: (BigInt, BigInt), v2: (BigInt, BigInt)) -> Self {
+        let n = (&v1.0 * &v2.1 - &v2.0 * &v1.1).magnitude().clone();
+        GlvEndomorphism {
+            beta,
+            lambda,
+            v1,
+            v2,
+            n,
+        }
+    }
+
+    pub fn beta(&self) -> &BigUint {
+        &self.beta
+    }
+
+    pub fn lambda(&self) -> &BigUint {
+        &self.lambda
+    }
+
+    pub fn basis(&self) -> (&(BigInt, BigInt), &(BigInt, BigInt)) {
+        (&self.v1, &self.v2)
+    }
+
+    // the order the basis was computed for
+    pub fn order(&self) -> &BigUint {
+        &self.n
+    }
+
+    // k = k1 + k2 lambda (mod n) with |k1|, |k2| about sqrt(n): subtract the
+    // lattice vector closest to (k, 0), found by rounding (k, 0) in the basis.
+    pub fn decompose(&self, k: &BigUint) -> (BigInt, BigInt) {
+        let n = BigInt::from(self.n.clone());
+        let k = BigInt::from(k % &self.n);
+        let (a1, b1) = &self.v1;
+        let (a2, b2) = &self.v2;
+        let c1 = round_div(&(b2 * &k), &n);
+        let c2 = round_div(&(-b1 * &k), &n);
+        let k1 = &k - &c1 * a1 - &c2 * a2;
+        let k2 = -(&c1 * b1) - &c2 * b2;
+        (k1, k2)
+    }
+
+    // true if the basis vectors lie in the lattice of lambda
+    pub(crate) fn basis_in_lattice(&self) -> bool {
+        let n = BigInt::from(self.n.clone());
+        let lambda = BigInt::from(self.lambda.clone());
+        [&self.v1, &self.v2]
+            .iter()
+            .all(|(a, b)| (a + b * &lambda).mod_floor(&n) == BigInt::from(0))
+    }
+}
+
+// x / n rounded to the nearest integer, for n > 0
+fn round_div(x: &BigInt, n: &BigInt) -> BigInt {
+    (x * BigInt::from(2) + n).div_floor(&(n * BigInt::from(2)))
+}
+
+// wNAF digits of a signed scalar: those of |k|, negated if k < 0
+pub(crate) fn signed_wnaf(k: &BigInt, w: usize) -> Vec<i64> {
+    let digits = wnaf(k.magnitude(), w);
+    if k.sign() == Sign::Minus {
+        digits.into_iter().map(|digit| -digit).collect()
+    } else {
+        digits
+    }
+}
+
+impl EllipticCurve {
+    pub fn endomorphism(&self) -> Option<&GlvEndomorphism> {
+        self.glv.as_deref()
+    }
+
+    // only DomainParameters::with_endomorphism, after checking it
+    pub(crate) fn set_endomorphism(&mut self, glv: GlvEndomorphism) {
+        self.glv = Some(Box::new(glv));
+    }
+
+    // phi(x, y) = (beta x, y); panics if the curve has no endomorphism
+    pub fn apply_endomorphism(&self, c: &Point) -> Point {
+        let glv = self.endomorphism().expect("curve has no endomorphism");
+        match c {
+            Point::Coordinates(x, y) => {
+                let f = self.field();
+                Point::Coordinates((f.element(glv.beta()) * f.element(x)).value(), y.clone())
+            }
+            Point::Identity => Point::Identity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod glv_test {
+    use super::{BigInt, BigUint, EllipticCurve, Point};
+    use crate::curves;
+    use num_traits::Signed;
+    use rand::Rng;
+
+    // |k1| and |k2| are at most about sqrt(n) for a reduced basis
+    fn is_half_length(k: &BigInt, n: &BigUint) -> bool {
+        k.abs().bits() <= n.bits().div_ceil(2) + 1
+    }
+
+    #[test]
+    fn test_decompose() {
+        let params = curves::secp256k1();
+        let glv = params
+            .curve()
+            .endomorphism()
+            .expect("secp256k1 is registered with an endomorphism");
+        assert_eq!(glv.order(), params.order());
+
+        let n = BigInt::from(params.order().clone());
+        let lambda = BigInt::from(glv.lambda().clone());
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let k = BigUint::from_bytes_be(&rng.gen::<[u8; 32]>()) % params.order();
+            let (k1, k2) = glv.decompose(&k);
+            assert_eq!(
+                (&k1 + &k2 * &lambda - BigInt::from(k.clone())) % &n,
+                BigInt::from(0)
+            );
+            assert!(is_half_length(&k1, params.order()));
+            assert!(is_half_length(&k2, params.order()));
+        }
+    }
+
+    #[test]
+    fn test_endomorphism_is_lambda() {
+        let params = curves::secp256k1();
+        let ec = params.curve();
+        let glv = ec.endomorphism().expect("secp256k1 has an endomorphism");
+        let q = ec.scalar_mul(params.generator(), &BigUint::from(12345u32));
+        // lambda Q without the endomorphism
+        assert_eq!(
+            ec.apply_endomorphism(&q),
+            ec.ladder_scalar_mul(&q, glv.lambda(), 256)
+        );
+        assert_eq!(ec.apply_endomorphism(&Point::Identity), Point::Identity);
+    }
+
+    #[test]
+    fn test_matches_plain_scalar_mul() {
+        let params = curves::secp256k1();
+        let (ec, g) = (params.curve(), params.generator());
+        // the same curve without the endomorphism
+        let plain = EllipticCurve::new(ec.a().clone(), ec.b().clone(), ec.p().clone());
+        assert!(plain.endomorphism().is_none());
+
+        let mut rng = rand::thread_rng();
+        let q = ec.scalar_mul(g, &BigUint::from(777u32));
+        for _ in 0..10 {
+            let k = BigUint::from_bytes_be(&rng.gen::<[u8; 32]>());
+            assert_eq!(ec.scalar_mul(&q, &k), plain.scalar_mul(&q, &k));
+            assert_eq!(
+                ec.scalar_mul_vartime(&q, &k, 4),
+                plain.scalar_mul_vartime(&q, &k, 4)
+            );
+        }
+        let n_minus_one = params.order() - BigUint::from(1u32);
+        assert_eq!(ec.scalar_mul(g, &n_minus_one), ec.negate(g));
+        assert_eq!(ec.scalar_mul(g, params.order()), Point::Identity);
+    }
+}
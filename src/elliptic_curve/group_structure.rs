@@ -0,0 +1,271 @@
+use super::curve_group::CurveGroup;
+use super::domain_parameters::within_hasse_bound;
+use super::elliptic_curve::{EllipticCurve, Point};
+use super::primality::trial_division;
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use std::collections::HashSet;
+
+// largest p for which the points are listed one x at a time
+pub const MAX_ENUMERATION_PRIME: u64 = 1 << 20;
+
+// E(F_p) = Z/n1 x Z/n2 with n2 | n1. The generators are a point of order
+// n1 and, when n2 > 1, a point of order n2 independent of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupStructure {
+    order: BigUint,
+    invariants: (BigUint, BigUint),
+    generators: Vec<Point>,
+}
+
+impl GroupStructure {
+    pub fn order(&self) -> &BigUint {
+        &self.order
+    }
+
+    // (n1, n2)
+    pub fn invariants(&self) -> (&BigUint, &BigUint) {
+        (&self.invariants.0, &self.invariants.1)
+    }
+
+    pub fn is_cyclic(&self) -> bool {
+        self.invariants.1 == BigUint::from(1u32)
+    }
+
+    pub fn generators(&self) -> &[Point] {
+        &self.generators
+    }
+}
+
+impl EllipticCurve {
+    pub fn enumerate_points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.try_enumerate_points()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // The identity, then every (x, y) in order of x, smaller y first.
+    // For teaching-sized curves only: p must be at most MAX_ENUMERATION_PRIME.
+    pub fn try_enumerate_points(&self) -> Result<impl Iterator<Item = Point> + '_> {
+        let p = self.enumerable_modulus()?;
+        let f = self.field();
+        let points = (0..p).flat_map(move |x| {
+            let x = BigUint::from(x);
+            match f.sqrt(&self.rhs(&x)) {
+                Some((y0, y1)) if y0 == y1 => vec![Point::Coordinates(x, y0)],
+                Some((y0, y1)) => {
+                    vec![Point::Coordinates(x.clone(), y0), Point::Coordinates(x, y1)]
+                }
+                None => vec![],
+            }
+        });
+        Ok(std::iter::once(Point::Identity).chain(points))
+    }
+
+    pub fn count_points(&self) -> BigUint {
+        self.try_count_points().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // #E = p + 1 + sum over x of the Legendre symbol of x^3 + ax + b
+    pub fn try_count_points(&self) -> Result<BigUint> {
+        let p = self.enumerable_modulus()?;
+        let f = self.field();
+        let trace: i64 = (0..p)
+            .map(|x| f.legendre(&self.rhs(&BigUint::from(x))) as i64)
+            .sum();
+        let count = BigUint::from((p as i64 + 1 + trace) as u64);
+        if !within_hasse_bound(self.p(), &count) {
+            return Err(EccError::HasseBoundViolated);
+        }
+        Ok(count)
+    }
+
+    pub fn group_structure(&self) -> GroupStructure {
+        self.try_group_structure()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // n1 is the group exponent, the largest point order; n2 = #E / n1.
+    // Finds the orders of all points, so it is quadratic-ish in p.
+    pub fn try_group_structure(&self) -> Result<GroupStructure> {
+        let order = self.try_count_points()?;
+        let factorization = trial_division(&order);
+
+        let mut orders = Vec::new();
+        for c in self.try_enumerate_points()? {
+            let point_order = self.try_point_order(&c, &factorization)?;
+            if point_order == order {
+                return Ok(GroupStructure {
+                    order,
+                    invariants: (point_order, BigUint::from(1u32)),
+                    generators: vec![c],
+                });
+            }
+            orders.push((c, point_order));
+        }
+
+        // not cyclic: take a point P of maximal order n1, then a point Q of
+        // order n2 with <P> and <Q> meeting only in the identity
+        let (p, n1) = orders
+            .iter()
+            .max_by(|a, b| a.1.cmp(&b.1))
+            .cloned()
+            .expect("the identity is always enumerated");
+        let n2 = &order / &n1;
+        let span = self.multiples(&p)?;
+        let q = orders
+            .iter()
+            .filter(|(_, point_order)| point_order == &n2)
+            .map(|(c, _)| c)
+            .find(|c| {
+                self.multiples(c)
+                    .map(|multiples| multiples.iter().filter(|m| span.contains(m)).count() == 1)
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .ok_or(EccError::InvalidPointOrder)?;
+
+        Ok(GroupStructure {
+            order,
+            invariants: (n1, n2),
+            generators: vec![p, q],
+        })
+    }
+
+    // every point of the cyclic subgroup generated by c, the identity included
+    fn multiples(&self, c: &Point) -> Result<HashSet<Point>> {
+        let mut multiples = HashSet::from([Point::Identity]);
+        let mut t = c.clone();
+        while t != Point::Identity {
+            multiples.insert(t.clone());
+            t = self.try_add(&t, c)?;
+        }
+        Ok(multiples)
+    }
+
+    // x^3 + ax + b
+    pub(super) fn rhs(&self, x: &BigUint) -> BigUint {
+        let f = self.field();
+        let x = f.element(x);
+        (x.square() * &x + f.element(self.a()) * &x + f.element(self.b())).value()
+    }
+
+    fn enumerable_modulus(&self) -> Result<u64> {
+        self.validate()?;
+        match self.p().to_u64() {
+            Some(p) if p <= MAX_ENUMERATION_PRIME => Ok(p),
+            _ => Err(EccError::FieldTooLarge(self.p().clone())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod group_structure_test {
+    use super::{BigUint, EccError, EllipticCurve, Point};
+    use crate::elliptic_curve::CurveGroup;
+    use std::collections::HashSet;
+
+    fn point(x: u32, y: u32) -> Point {
+        Point::Coordinates(BigUint::from(x), BigUint::from(y))
+    }
+
+    #[test]
+    fn test_enumerate_points() {
+        // y^2 = x^3 + 2x + 2 mod 17
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+        let points: Vec<Point> = ec.enumerate_points().collect();
+        assert_eq!(points.len(), 19);
+        assert_eq!(points[0], Point::Identity);
+        assert_eq!(points[1], point(0, 6));
+        assert_eq!(points[2], point(0, 11));
+        assert!(points.iter().all(|c| ec.is_on_curve(c)));
+
+        // the multiples of (5, 1) are the whole group
+        let mut multiples = HashSet::from([Point::Identity]);
+        let mut t = point(5, 1);
+        for _ in 1..19 {
+            multiples.insert(t.clone());
+            t = ec.add(&t, &point(5, 1));
+        }
+        assert_eq!(multiples, points.into_iter().collect());
+        assert_eq!(ec.count_points(), BigUint::from(19u32));
+    }
+
+    #[test]
+    fn test_cyclic_group() {
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+        let structure = ec.group_structure();
+        assert_eq!(structure.order(), &BigUint::from(19u32));
+        assert!(structure.is_cyclic());
+        assert_eq!(structure.generators(), &[point(0, 6)]);
+
+        // y^2 = x^3 + 2x + 3 mod 17: 22 points, (2, 7) has order 22
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(3u32),
+            BigUint::from(17u32),
+        );
+        let structure = ec.group_structure();
+        assert_eq!(
+            structure.invariants(),
+            (&BigUint::from(22u32), &BigUint::from(1u32))
+        );
+        let generator = &structure.generators()[0];
+        assert_eq!(
+            ec.try_point_order(
+                generator,
+                &[(BigUint::from(2u32), 1), (BigUint::from(11u32), 1)]
+            ),
+            Ok(BigUint::from(22u32))
+        );
+    }
+
+    #[test]
+    fn test_non_cyclic_group() {
+        // y^2 = x^3 - x has full 2-torsion: Z/4 x Z/4 over F_17,
+        // Z/12 x Z/2 over F_23
+        for (p, n1, n2) in [(17u32, 4u32, 4u32), (23, 12, 2)] {
+            let ec =
+                EllipticCurve::new(BigUint::from(p - 1), BigUint::from(0u32), BigUint::from(p));
+            let structure = ec.group_structure();
+            assert_eq!(structure.order(), &BigUint::from(n1 * n2));
+            assert_eq!(
+                structure.invariants(),
+                (&BigUint::from(n1), &BigUint::from(n2))
+            );
+            assert!(!structure.is_cyclic());
+            // n2 divides p - 1, as the Weil pairing requires
+            assert_eq!((p - 1) % n2, 0);
+
+            // the two generators span every point
+            let (g1, g2) = (&structure.generators()[0], &structure.generators()[1]);
+            let mut span = HashSet::new();
+            for i in 0..n1 {
+                for j in 0..n2 {
+                    span.insert(ec.add(
+                        &ec.scalar_mul(g1, &BigUint::from(i)),
+                        &ec.scalar_mul(g2, &BigUint::from(j)),
+                    ));
+                }
+            }
+            assert_eq!(span, ec.enumerate_points().collect());
+        }
+    }
+
+    #[test]
+    fn test_field_too_large() {
+        let ec = crate::curves::secp256k1().curve();
+        assert_eq!(
+            ec.try_count_points().err(),
+            Some(EccError::FieldTooLarge(ec.p().clone()))
+        );
+    }
+}
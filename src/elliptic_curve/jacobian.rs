@@ -0,0 +1,292 @@
+use super::elliptic_curve::{EllipticCurve, Point};
+use super::field_element::FieldElement;
+use crate::error::Result;
+use num_bigint::BigUint;
+
+// (X, Y, Z) stands for the affine point (X / Z^2, Y / Z^3). Z = 0 is the
+// point at infinity, so the group law needs no inversion until to_affine.
+#[derive(Debug, Clone)]
+pub struct JacobianPoint<'a> {
+    x: FieldElement<'a>,
+    y: FieldElement<'a>,
+    z: FieldElement<'a>,
+}
+
+impl<'a> JacobianPoint<'a> {
+    pub fn x(&self) -> &FieldElement<'a> {
+        &self.x
+    }
+
+    pub fn y(&self) -> &FieldElement<'a> {
+        &self.y
+    }
+
+    pub fn z(&self) -> &FieldElement<'a> {
+        &self.z
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+}
+
+// Two representatives are equal if X1 Z2^2 = X2 Z1^2 and Y1 Z2^3 = Y2 Z1^3.
+impl PartialEq for JacobianPoint<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_identity() || other.is_identity() {
+            return self.is_identity() && other.is_identity();
+        }
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+        &self.x * &z2z2 == &other.x * &z1z1
+            && &self.y * &z2z2 * &other.z == &other.y * &z1z1 * &self.z
+    }
+}
+
+impl Eq for JacobianPoint<'_> {}
+
+impl EllipticCurve {
+    pub fn jacobian_identity(&self) -> JacobianPoint<'_> {
+        let f = self.field();
+        JacobianPoint {
+            x: f.one(),
+            y: f.one(),
+            z: f.zero(),
+        }
+    }
+
+    // (x, y) -> (x, y, 1); does not check that c is on the curve
+    pub fn to_jacobian(&self, c: &Point) -> JacobianPoint<'_> {
+        let f = self.field();
+        match c {
+            Point::Coordinates(x, y) => JacobianPoint {
+                x: f.element(x),
+                y: f.element(y),
+                z: f.one(),
+            },
+            Point::Identity => self.jacobian_identity(),
+        }
+    }
+
+    pub fn to_affine(&self, c: &JacobianPoint) -> Point {
+        self.try_to_affine(c).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // x = X / Z^2, y = Y / Z^3, one inversion
+    pub fn try_to_affine(&self, c: &JacobianPoint) -> Result<Point> {
+        if c.is_identity() {
+            return Ok(Point::Identity);
+        }
+        let z_inv = c.z.try_inv()?;
+        let z_inv2 = z_inv.square();
+        let x = &c.x * &z_inv2;
+        let y = &c.y * &z_inv2 * &z_inv;
+        Ok(Point::Coordinates(x.value(), y.value()))
+    }
+
+    // Normalizes many points with one shared inversion (Montgomery's trick).
+    pub fn batch_to_affine(&self, points: &[JacobianPoint]) -> Result<Vec<Point>> {
+        let z: Vec<FieldElement> = points
+            .iter()
+            .filter(|c| !c.is_identity())
+            .map(|c| c.z.clone())
+            .collect();
+        let mut z_inv = FieldElement::batch_inv(&z)?.into_iter();
+
+        Ok(points
+            .iter()
+            .map(|c| {
+                if c.is_identity() {
+                    return Point::Identity;
+                }
+                let z_inv = z_inv.next().expect("one inverse per finite point");
+                let z_inv2 = z_inv.square();
+                Point::Coordinates((&c.x * &z_inv2).value(), (&c.y * &z_inv2 * &z_inv).value())
+            })
+            .collect())
+    }
+
+    // dbl-2007-bl: 1M + 8S for any a
+    pub fn jacobian_double<'a>(&'a self, c: &JacobianPoint<'a>) -> JacobianPoint<'a> {
+        if c.is_identity() || c.y.is_zero() {
+            return self.jacobian_identity();
+        }
+        let f = self.field();
+
+        let xx = c.x.square();
+        let yy = c.y.square();
+        let yyyy = yy.square();
+        let zz = c.z.square();
+        // s = 2((X + YY)^2 - XX - YYYY)
+        let s = (&c.x + &yy).square() - &xx - &yyyy;
+        let s = &s + &s;
+        // m = 3 XX + a ZZ^2
+        let m = &xx + &xx + &xx + f.element(self.a()) * zz.square();
+
+        let x3 = m.square() - &s - &s;
+        let yyyy8 = &yyyy + &yyyy;
+        let yyyy8 = &yyyy8 + &yyyy8;
+        let yyyy8 = &yyyy8 + &yyyy8;
+        let y3 = &m * (&s - &x3) - yyyy8;
+        let z3 = (&c.y + &c.z).square() - yy - zz;
+        JacobianPoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    // add-2007-bl: 11M + 5S, falls back to doubling when r == q
+    pub fn jacobian_add<'a>(
+        &'a self,
+        r: &JacobianPoint<'a>,
+        q: &JacobianPoint<'a>,
+    ) -> JacobianPoint<'a> {
+        if r.is_identity() {
+            return q.clone();
+        }
+        if q.is_identity() {
+            return r.clone();
+        }
+
+        let z1z1 = r.z.square();
+        let z2z2 = q.z.square();
+        let u1 = &r.x * &z2z2;
+        let u2 = &q.x * &z1z1;
+        let s1 = &r.y * &q.z * &z2z2;
+        let s2 = &q.y * &r.z * &z1z1;
+        let h = &u2 - &u1;
+        let r_ = &s2 - &s1;
+        if h.is_zero() {
+            // same x: either q = r or q = -r
+            if r_.is_zero() {
+                return self.jacobian_double(r);
+            }
+            return self.jacobian_identity();
+        }
+
+        let h2 = &h + &h;
+        let i = h2.square();
+        let j = &h * &i;
+        let rr = &r_ + &r_;
+        let v = &u1 * &i;
+        let x3 = rr.square() - &j - &v - &v;
+        let s1j = &s1 * &j;
+        let y3 = &rr * (&v - &x3) - &s1j - &s1j;
+        let z3 = ((&r.z + &q.z).square() - z1z1 - z2z2) * h;
+        JacobianPoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    // madd-2007-bl: r in Jacobian, q affine (Z2 = 1), 7M + 4S
+    pub fn jacobian_add_mixed<'a>(&'a self, r: &JacobianPoint<'a>, q: &Point) -> JacobianPoint<'a> {
+        let (qx, qy) = match q {
+            Point::Coordinates(x, y) => (x, y),
+            Point::Identity => return r.clone(),
+        };
+        if r.is_identity() {
+            return self.to_jacobian(q);
+        }
+        let f = self.field();
+        let (qx, qy) = (f.element(qx), f.element(qy));
+
+        let z1z1 = r.z.square();
+        let u2 = &qx * &z1z1;
+        let s2 = &qy * &r.z * &z1z1;
+        let h = &u2 - &r.x;
+        let r_ = &s2 - &r.y;
+        if h.is_zero() {
+            if r_.is_zero() {
+                return self.jacobian_double(r);
+            }
+            return self.jacobian_identity();
+        }
+
+        let hh = h.square();
+        let i = &hh + &hh;
+        let i = &i + &i;
+        let j = &h * &i;
+        let rr = &r_ + &r_;
+        let v = &r.x * &i;
+        let x3 = rr.square() - &j - &v - &v;
+        let y1j = &r.y * &j;
+        let y3 = &rr * (&v - &x3) - &y1j - &y1j;
+        let z3 = (&r.z + &h).square() - z1z1 - hh;
+        JacobianPoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    // Double-and-add in Jacobian coordinates; c must already be on the curve.
+    pub(crate) fn jacobian_scalar_mul(&self, c: &Point, d: &BigUint) -> Result<Point> {
+        let mut t = self.to_jacobian(c);
+        for i in (0..d.bits().saturating_sub(1)).rev() {
+            t = self.jacobian_double(&t);
+            if d.bit(i) {
+                t = self.jacobian_add_mixed(&t, c);
+            }
+        }
+        self.try_to_affine(&t)
+    }
+}
+
+#[cfg(test)]
+mod jacobian_test {
+    use super::{BigUint, EllipticCurve, Point};
+
+    fn get_test_curve() -> EllipticCurve {
+        EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        )
+    }
+
+    #[test]
+    fn test_matches_affine() {
+        let ec = get_test_curve();
+        let g = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
+
+        // walk the whole group of order 19 with affine and Jacobian arithmetic
+        let mut affine = g.clone();
+        let mut jacobian = ec.to_jacobian(&g);
+        for _ in 2..=19 {
+            affine = if affine == g {
+                ec.double(&affine)
+            } else {
+                ec.add(&affine, &g)
+            };
+            jacobian = ec.jacobian_add_mixed(&jacobian, &g);
+            assert_eq!(ec.to_affine(&jacobian), affine);
+            assert_eq!(
+                ec.to_affine(&ec.jacobian_double(&jacobian)),
+                ec.double(&affine)
+            );
+        }
+        assert!(jacobian.is_identity());
+    }
+
+    #[test]
+    fn test_full_addition() {
+        let ec = get_test_curve();
+        let p1 = Point::Coordinates(BigUint::from(6u32), BigUint::from(3u32));
+        let p2 = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
+
+        // move both points off Z = 1 before adding
+        let j1 = ec.jacobian_double(&ec.to_jacobian(&p1));
+        let j2 = ec.jacobian_double(&ec.to_jacobian(&p2));
+        let expected = ec.add(&ec.double(&p1), &ec.double(&p2));
+        assert_eq!(ec.to_affine(&ec.jacobian_add(&j1, &j2)), expected);
+
+        // r + r doubles, r + (-r) is the identity
+        assert_eq!(ec.jacobian_add(&j1, &j1), ec.jacobian_double(&j1));
+        let neg = ec.to_jacobian(&ec.negate(&ec.double(&p1)));
+        assert!(ec.jacobian_add(&j1, &neg).is_identity());
+        assert_eq!(ec.jacobian_add(&j1, &ec.jacobian_identity()), j1);
+    }
+}
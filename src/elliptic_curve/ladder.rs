@@ -0,0 +1,113 @@
+use super::curve_group::check_ladder_bits;
+use super::elliptic_curve::{EllipticCurve, Point};
+use super::field_element::FieldElement;
+use super::projective::ProjectivePoint;
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+
+// Swaps r and q when bit is one using only field arithmetic:
+// t = bit * (q - r), r += t, q -= t. The same operations run for both bit
+// values, so there is no branch on the secret bit. BigUint itself is not
+// constant-time, so this removes the control-flow leak, not every leak.
+pub(super) fn conditional_swap<'a>(
+    bit: &FieldElement<'a>,
+    r: &mut FieldElement<'a>,
+    q: &mut FieldElement<'a>,
+) {
+    let t = bit * (&*q - &*r);
+    *r += &t;
+    *q -= &t;
+}
+
+impl EllipticCurve {
+    pub fn ladder_scalar_mul(&self, c: &Point, d: &BigUint, bits: u64) -> Point {
+        self.try_ladder_scalar_mul(c, d, bits)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // Montgomery ladder over exactly `bits` bits of d, for secret scalars.
+    // Each step is a conditional swap, one complete addition and one complete
+    // doubling, so the sequence of field operations depends only on `bits`.
+    // Relies on the complete formulas, i.e. on curves of odd order.
+    pub fn try_ladder_scalar_mul(&self, c: &Point, d: &BigUint, bits: u64) -> Result<Point> {
+        if !self.is_on_curve(c) {
+            return Err(EccError::NotOnCurve(c.clone()));
+        }
+        check_ladder_bits(d, bits)?;
+
+        let f = self.field();
+        let mut r0 = self.projective_identity();
+        let mut r1 = self.to_projective(c);
+        for i in (0..bits).rev() {
+            let bit = f.element(&BigUint::from(d.bit(i) as u8));
+            swap_points(&bit, &mut r0, &mut r1);
+            r1 = self.complete_add(&r0, &r1);
+            r0 = self.complete_add(&r0, &r0);
+            swap_points(&bit, &mut r0, &mut r1);
+        }
+        self.try_projective_to_affine(&r0)
+    }
+}
+
+fn swap_points<'a>(
+    bit: &FieldElement<'a>,
+    r: &mut ProjectivePoint<'a>,
+    q: &mut ProjectivePoint<'a>,
+) {
+    let (rx, ry, rz) = r.coordinates_mut();
+    let (qx, qy, qz) = q.coordinates_mut();
+    conditional_swap(bit, rx, qx);
+    conditional_swap(bit, ry, qy);
+    conditional_swap(bit, rz, qz);
+}
+
+#[cfg(test)]
+mod ladder_test {
+    use super::{BigUint, EccError, EllipticCurve, Point};
+    use crate::curves;
+
+    #[test]
+    fn test_matches_scalar_mul() {
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+        let g = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
+        for k in 0..40u32 {
+            let k = BigUint::from(k);
+            assert_eq!(ec.ladder_scalar_mul(&g, &k, 6), ec.scalar_mul(&g, &k));
+        }
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let params = curves::secp256k1();
+        let (ec, g) = (params.curve(), params.generator());
+        let k = BigUint::parse_bytes(
+            b"AA5E28D6A97A2479A65527F7290311A3624D4CC0FA1578598EE3C2613BF99522",
+            16,
+        )
+        .expect("could not convert str to k");
+        assert_eq!(ec.ladder_scalar_mul(g, &k, 256), ec.scalar_mul(g, &k));
+        // leading zero bits change nothing but the running time
+        assert_eq!(ec.ladder_scalar_mul(g, &k, 300), ec.scalar_mul(g, &k));
+    }
+
+    #[test]
+    fn test_scalar_too_long() {
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+        let g = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
+        assert_eq!(
+            ec.try_ladder_scalar_mul(&g, &BigUint::from(16u32), 4),
+            Err(EccError::ScalarOutOfRange {
+                value: BigUint::from(16u32),
+                modulus: BigUint::from(16u32)
+            })
+        );
+    }
+}
@@ -1,5 +1,56 @@
+mod binary_curve;
+mod binary_field;
+mod curve_group;
+mod curve_point;
+mod domain_parameters;
+#[allow(clippy::module_inception)]
 mod elliptic_curve;
+mod extension_field;
+mod field;
+mod field_element;
 mod finite_field;
+mod fixed_base;
+mod glv;
+mod group_structure;
+mod jacobian;
+mod ladder;
+mod montgomery_curve;
+mod montgomery_form;
+mod multi_scalar;
+mod ntt;
+mod pippenger;
+mod polynomial;
+mod primality;
+mod projective;
+mod schoof;
+mod twisted_edwards;
+mod u256;
+mod wnaf;
 
+pub use binary_curve::BinaryCurve;
+pub use binary_field::BinaryField;
+pub use curve_group::CurveGroup;
+pub use curve_point::CurvePoint;
+pub use domain_parameters::DomainParameters;
 pub use elliptic_curve::{EllipticCurve, Point};
+pub use extension_field::{
+    tower, CubicElement, CubicExtension, Fp12, Fp12Element, Fp2, Fp2Element, Fp6, Fp6Element,
+    QuadraticElement, QuadraticExtension,
+};
+pub use field::Field;
+pub use field_element::FieldElement;
 pub use finite_field::FiniteField;
+pub use fixed_base::{FixedBaseTable, FIXED_BASE_WINDOW};
+pub use glv::GlvEndomorphism;
+pub use group_structure::{GroupStructure, MAX_ENUMERATION_PRIME};
+pub use jacobian::JacobianPoint;
+pub use montgomery_curve::MontgomeryCurve;
+pub use montgomery_form::MontgomeryForm;
+pub use pippenger::pippenger_window;
+pub use primality::is_prime;
+pub use projective::ProjectivePoint;
+pub use twisted_edwards::{ExtendedPoint, TwistedEdwardsCurve};
+pub use u256::{SpecialPrime, U256};
+pub use wnaf::{wnaf, DEFAULT_WNAF_WIDTH};
+
+pub(crate) use wnaf::check_wnaf_width;
@@ -0,0 +1,172 @@
+use super::curve_group::check_ladder_bits;
+use super::finite_field::FiniteField;
+use super::ladder::conditional_swap;
+use super::primality::is_prime;
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+
+// Montgomery curve B v^2 = u^3 + A u^2 + u over F_p (RFC 7748). Points are
+// handled by their u-coordinate alone: u(kP) depends only on u(P), and the
+// ladder below computes it without ever telling P from -P.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MontgomeryCurve {
+    a: BigUint,
+    b: BigUint,
+    field: FiniteField,
+}
+
+impl MontgomeryCurve {
+    pub fn new(a: BigUint, b: BigUint, p: BigUint) -> Self {
+        MontgomeryCurve {
+            a,
+            b,
+            field: FiniteField::new(p),
+        }
+    }
+
+    pub fn a(&self) -> &BigUint {
+        &self.a
+    }
+
+    pub fn b(&self) -> &BigUint {
+        &self.b
+    }
+
+    pub fn p(&self) -> &BigUint {
+        self.field.p()
+    }
+
+    pub fn field(&self) -> &FiniteField {
+        &self.field
+    }
+
+    // p must be an odd prime, B != 0 and A^2 != 4 (mod p)
+    pub fn validate(&self) -> Result<()> {
+        if !is_prime(self.p()) || self.p() == &BigUint::from(2u32) {
+            return Err(EccError::ModulusNotPrime);
+        }
+        let f = &self.field;
+        let (a, b) = (f.element(&self.a), f.element(&self.b));
+        if b.is_zero() || (a.square() - f.element(&BigUint::from(4u32))).is_zero() {
+            return Err(EccError::SingularCurve);
+        }
+        Ok(())
+    }
+
+    pub fn ladder(&self, k: &BigUint, u: &BigUint, bits: u64) -> BigUint {
+        self.try_ladder(k, u, bits)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // u(kP) from u = u(P) over exactly `bits` bits of k, with the
+    // differential addition and doubling of RFC 7748, section 5. Every step
+    // runs the same field operations, the swaps included. The identity
+    // comes out as 0, since z^(p - 2) = 0 for z = 0.
+    pub fn try_ladder(&self, k: &BigUint, u: &BigUint, bits: u64) -> Result<BigUint> {
+        check_ladder_bits(k, bits)?;
+
+        let f = &self.field;
+        // (A - 2) / 4
+        let a24 = (f.element(&self.a) - f.element(&BigUint::from(2u32)))
+            .try_div(&f.element(&BigUint::from(4u32)))?;
+        let x1 = f.element(u);
+        let (mut x2, mut z2) = (f.one(), f.zero());
+        let (mut x3, mut z3) = (x1.clone(), f.one());
+        let mut swap = false;
+        for i in (0..bits).rev() {
+            let bit = k.bit(i);
+            let flag = f.element(&BigUint::from((swap ^ bit) as u8));
+            conditional_swap(&flag, &mut x2, &mut x3);
+            conditional_swap(&flag, &mut z2, &mut z3);
+            swap = bit;
+
+            let a = &x2 + &z2;
+            let aa = a.square();
+            let b = &x2 - &z2;
+            let bb = b.square();
+            let e = &aa - &bb;
+            let c = &x3 + &z3;
+            let d = &x3 - &z3;
+            let da = d * a;
+            let cb = c * b;
+            x3 = (&da + &cb).square();
+            z3 = &x1 * (&da - &cb).square();
+            z2 = &e * (&aa + &a24 * &e);
+            x2 = aa * bb;
+        }
+        let flag = f.element(&BigUint::from(swap as u8));
+        conditional_swap(&flag, &mut x2, &mut x3);
+        conditional_swap(&flag, &mut z2, &mut z3);
+
+        Ok((x2 * z2.pow(&(self.p() - 2u32))).value())
+    }
+}
+
+#[cfg(test)]
+mod montgomery_curve_test {
+    use super::{BigUint, EccError, MontgomeryCurve};
+    use crate::elliptic_curve::{EllipticCurve, Point};
+    use num_bigint::RandBigInt;
+
+    #[test]
+    fn test_validate() {
+        let p = BigUint::from(101u32);
+        let curve = |a: u32, b: u32, p: &BigUint| {
+            MontgomeryCurve::new(BigUint::from(a), BigUint::from(b), p.clone()).validate()
+        };
+        assert_eq!(curve(3, 1, &p), Ok(()));
+        // A = +-2 and B = 0 are singular
+        assert_eq!(curve(2, 1, &p), Err(EccError::SingularCurve));
+        assert_eq!(curve(99, 1, &p), Err(EccError::SingularCurve));
+        assert_eq!(curve(3, 0, &p), Err(EccError::SingularCurve));
+        assert_eq!(
+            curve(3, 1, &BigUint::from(100u32)),
+            Err(EccError::ModulusNotPrime)
+        );
+    }
+
+    #[test]
+    fn test_matches_weierstrass() {
+        // with B = 1, (u, v) -> (u + A/3, v) maps onto
+        // y^2 = x^3 + (3 - A^2)/3 x + (2A^3 - 9A)/27
+        let curve = crate::curves::curve25519();
+        let f = curve.field();
+        let a = f.element(curve.a());
+        let three = f.element(&BigUint::from(3u32));
+        let shift = &a / &three;
+        let wa = (&three - a.square()) / &three;
+        let wb = (f.element(&BigUint::from(2u32)) * a.square() * &a
+            - f.element(&BigUint::from(9u32)) * &a)
+            / f.element(&BigUint::from(27u32));
+        let ec = EllipticCurve::new(wa.value(), wb.value(), curve.p().clone());
+
+        let u = f.element(&BigUint::from(9u32));
+        let x = &u + &shift;
+        let (y, _) = f
+            .sqrt(&(x.square() * &x + &wa * &x + &wb).value())
+            .expect("the base point is on the curve");
+        let g = Point::Coordinates(x.value(), y);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..4 {
+            let k = rng.gen_biguint(255);
+            let expected = match ec.scalar_mul(&g, &k) {
+                Point::Coordinates(x, _) => (f.element(&x) - &shift).value(),
+                Point::Identity => BigUint::from(0u32),
+            };
+            assert_eq!(curve.ladder(&k, &u.value(), 255), expected);
+        }
+    }
+
+    #[test]
+    fn test_scalar_too_long() {
+        let curve = crate::curves::curve25519();
+        assert_eq!(
+            curve.try_ladder(&BigUint::from(16u32), &BigUint::from(9u32), 4),
+            Err(EccError::ScalarOutOfRange {
+                value: BigUint::from(16u32),
+                modulus: BigUint::from(16u32)
+            })
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use num_bigint::BigUint;
+
+// Precomputed constants for Montgomery multiplication modulo an odd p.
+// A residue a is stored as a * R mod p, where R = 2^r_bits > p, so that
+// a product only needs a multiplication, a mask and a shift to reduce (REDC)
+// instead of a full division.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MontgomeryForm {
+    p: BigUint,
+    // R = 2^r_bits, r_bits is a multiple of the 64-bit limb size
+    r_bits: u64,
+    // R - 1, used to compute "mod R"
+    mask: BigUint,
+    // R mod p, the Montgomery form of 1
+    r: BigUint,
+    // R^2 mod p, used to convert into Montgomery form
+    r2: BigUint,
+    // -p^(-1) mod R
+    n_prime: BigUint,
+}
+
+impl MontgomeryForm {
+    // Returns None for even moduli, which have no inverse modulo R.
+    pub fn new(p: &BigUint) -> Option<Self> {
+        if !p.bit(0) || p == &BigUint::from(1u32) {
+            return None;
+        }
+
+        let r_bits = p.bits().div_ceil(64) * 64;
+        let one = BigUint::from(1u32);
+        let r_full = &one << r_bits;
+        let mask = &r_full - &one;
+
+        // Newton iteration for p^(-1) mod R, each step doubles the correct low bits
+        let mut p_inv = one.clone();
+        let mut correct_bits = 1;
+        while correct_bits < r_bits {
+            let correction = (&r_full + BigUint::from(2u32) - ((p * &p_inv) & &mask)) & &mask;
+            p_inv = (p_inv * correction) & &mask;
+            correct_bits *= 2;
+        }
+        let n_prime = (&r_full - p_inv) & &mask;
+
+        let r = &r_full % p;
+        let r2 = (&r * &r) % p;
+
+        Some(MontgomeryForm {
+            p: p.clone(),
+            r_bits,
+            mask,
+            r,
+            r2,
+            n_prime,
+        })
+    }
+
+    // a -> a * R mod p
+    pub fn to_montgomery(&self, a: &BigUint) -> BigUint {
+        self.mul(&(a % &self.p), &self.r2)
+    }
+
+    // a * R -> a mod p
+    pub fn from_montgomery(&self, a: &BigUint) -> BigUint {
+        self.redc(a.clone())
+    }
+
+    // Montgomery form of 1
+    pub fn one(&self) -> &BigUint {
+        &self.r
+    }
+
+    // (a * R) * (b * R) -> a * b * R mod p
+    pub fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        self.redc(a * b)
+    }
+
+    // square-and-multiply on Montgomery residues
+    pub fn pow(&self, base: &BigUint, exponent: &BigUint) -> BigUint {
+        let mut result = self.r.clone();
+        for i in (0..exponent.bits()).rev() {
+            result = self.mul(&result, &result);
+            if exponent.bit(i) {
+                result = self.mul(&result, base);
+            }
+        }
+        result
+    }
+
+    // t -> t * R^(-1) mod p, for t < p * R
+    fn redc(&self, t: BigUint) -> BigUint {
+        let m = ((&t & &self.mask) * &self.n_prime) & &self.mask;
+        let u = (t + m * &self.p) >> self.r_bits;
+        if u >= self.p {
+            u - &self.p
+        } else {
+            u
+        }
+    }
+}
+
+#[cfg(test)]
+mod montgomery_form_test {
+    use super::{BigUint, MontgomeryForm};
+
+    #[test]
+    fn test_even_modulus() {
+        assert_eq!(MontgomeryForm::new(&BigUint::from(32u32)), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let m = MontgomeryForm::new(&BigUint::from(17u32)).expect("17 is odd");
+        for a in 0..17u32 {
+            let a = BigUint::from(a);
+            assert_eq!(m.from_montgomery(&m.to_montgomery(&a)), a);
+        }
+        assert_eq!(m.from_montgomery(m.one()), BigUint::from(1u32));
+    }
+
+    #[test]
+    fn test_mul_matches_plain() {
+        let p = BigUint::from(17u32);
+        let m = MontgomeryForm::new(&p).expect("17 is odd");
+        for a in 0..17u32 {
+            for b in 0..17u32 {
+                let product = m.mul(
+                    &m.to_montgomery(&BigUint::from(a)),
+                    &m.to_montgomery(&BigUint::from(b)),
+                );
+                assert_eq!(m.from_montgomery(&product), BigUint::from(a * b % 17));
+            }
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_prime() {
+        let p = BigUint::parse_bytes(
+            b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .expect("could not convert str to p");
+        let m = MontgomeryForm::new(&p).expect("p is odd");
+        let a = BigUint::parse_bytes(
+            b"79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            16,
+        )
+        .expect("could not convert str to a");
+        let b = BigUint::parse_bytes(
+            b"483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+            16,
+        )
+        .expect("could not convert str to b");
+
+        let product = m.mul(&m.to_montgomery(&a), &m.to_montgomery(&b));
+        assert_eq!(m.from_montgomery(&product), (&a * &b) % &p);
+
+        let e = BigUint::from(65537u32);
+        let power = m.pow(&m.to_montgomery(&a), &e);
+        assert_eq!(m.from_montgomery(&power), a.modpow(&e, &p));
+    }
+}
@@ -0,0 +1,145 @@
+use super::elliptic_curve::{EllipticCurve, Point};
+use super::glv::signed_wnaf;
+use super::wnaf::{wnaf, DEFAULT_WNAF_WIDTH};
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+
+impl EllipticCurve {
+    // k1 P1 + k2 P2 + ... for public scalars
+    pub fn multi_scalar_mul(&self, terms: &[(Point, BigUint)]) -> Point {
+        self.try_multi_scalar_mul(terms)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_multi_scalar_mul(&self, terms: &[(Point, BigUint)]) -> Result<Point> {
+        self.try_multi_scalar_mul_windowed(terms, DEFAULT_WNAF_WIDTH)
+    }
+
+    // Straus/Shamir: the wNAF expansions of all scalars are walked together,
+    // so the doublings are shared and only the additions scale with the
+    // number of terms. Variable time, like scalar_mul_vartime. With a GLV
+    // endomorphism each term k P becomes k1 P + k2 phi(P) with half-length
+    // k1, k2, which halves the doubling chain; phi is applied to the table
+    // of P instead of building a second one.
+    pub fn try_multi_scalar_mul_windowed(
+        &self,
+        terms: &[(Point, BigUint)],
+        w: usize,
+    ) -> Result<Point> {
+        if let Some((c, _)) = terms.iter().find(|(c, _)| !self.is_on_curve(c)) {
+            return Err(EccError::NotOnCurve(c.clone()));
+        }
+
+        let mut tables = Vec::with_capacity(terms.len());
+        let mut digits = Vec::with_capacity(terms.len());
+        for (c, k) in terms {
+            let table = self.odd_multiples(c, w)?;
+            match self.endomorphism() {
+                Some(glv) => {
+                    let (k1, k2) = glv.decompose(k);
+                    tables.push(table.map(|q| self.apply_endomorphism(q)));
+                    digits.push(signed_wnaf(&k2, w));
+                    tables.push(table);
+                    digits.push(signed_wnaf(&k1, w));
+                }
+                None => {
+                    tables.push(table);
+                    digits.push(wnaf(k, w));
+                }
+            }
+        }
+        let length = digits.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut t = self.jacobian_identity();
+        for i in (0..length).rev() {
+            t = self.jacobian_double(&t);
+            for (table, digits) in tables.iter().zip(&digits) {
+                match digits.get(i) {
+                    Some(&digit) if digit != 0 => {
+                        t = self.jacobian_add_mixed(&t, table.get(digit));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        self.try_to_affine(&t)
+    }
+}
+
+#[cfg(test)]
+mod multi_scalar_test {
+    use super::{BigUint, EccError, EllipticCurve, Point};
+    use crate::curves;
+
+    #[test]
+    fn test_matches_separate_products() {
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+        let g = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
+        let q = ec.scalar_mul(&g, &BigUint::from(7u32));
+
+        for k1 in 0..25u32 {
+            for k2 in 0..25u32 {
+                let (k1, k2) = (BigUint::from(k1), BigUint::from(k2));
+                let expected = ec.add(&ec.scalar_mul(&g, &k1), &ec.scalar_mul(&q, &k2));
+                let terms = [(g.clone(), k1), (q.clone(), k2)];
+                assert_eq!(ec.multi_scalar_mul(&terms), expected);
+            }
+        }
+        assert_eq!(ec.multi_scalar_mul(&[]), Point::Identity);
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let params = curves::secp256k1();
+        let (ec, g) = (params.curve(), params.generator());
+        // without GLV, so the plain Straus loop is checked against separate
+        // products that do not go through the endomorphism either
+        let plain = EllipticCurve::new(ec.a().clone(), ec.b().clone(), ec.p().clone());
+        assert!(plain.endomorphism().is_none());
+        let scalars = [
+            "AA5E28D6A97A2479A65527F7290311A3624D4CC0FA1578598EE3C2613BF99522",
+            "7E2B897B8CEBC6361663AD410835639826D590F393D90A9538881735256DFAE3",
+            "06",
+        ]
+        .map(|k| BigUint::parse_bytes(k.as_bytes(), 16).expect("could not convert str to k"));
+
+        let points: Vec<Point> = (1..=3u32)
+            .map(|i| ec.scalar_mul(g, &BigUint::from(i * 1000)))
+            .collect();
+        let terms: Vec<(Point, BigUint)> = points.into_iter().zip(scalars).collect();
+
+        let expected = terms.iter().fold(Point::Identity, |acc, (c, k)| {
+            plain.add(&acc, &plain.scalar_mul(c, k))
+        });
+        assert_eq!(plain.multi_scalar_mul(&terms), expected);
+        assert_eq!(ec.multi_scalar_mul(&terms), expected);
+        for w in 2..=7 {
+            assert_eq!(
+                plain.try_multi_scalar_mul_windowed(&terms, w),
+                Ok(expected.clone())
+            );
+            assert_eq!(
+                ec.try_multi_scalar_mul_windowed(&terms, w),
+                Ok(expected.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn test_not_on_curve() {
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+        let off_curve = Point::Coordinates(BigUint::from(5u32), BigUint::from(2u32));
+        assert_eq!(
+            ec.try_multi_scalar_mul(&[(off_curve.clone(), BigUint::from(1u32))]),
+            Err(EccError::NotOnCurve(off_curve))
+        );
+    }
+}
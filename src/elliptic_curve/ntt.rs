@@ -0,0 +1,330 @@
+use num_bigint::BigUint;
+
+// NTT-friendly primes q = c 2^32 + 1 just below 2^62, each with a generator
+// of its multiplicative group. A product of polynomials over F_p is computed
+// modulo enough of them to hold its coefficients exactly (below
+// len p^2), then put back together by the CRT.
+const PRIMES: [(u64, u64); 20] = [
+    (4611685941117976577, 3),
+    (4611685692009873409, 19),
+    (4611685606110527489, 3),
+    (4611685318347718657, 5),
+    (4611685232448372737, 3),
+    (4611685219563470849, 3),
+    (4611685125074190337, 5),
+    (4611685090714451969, 3),
+    (4611685039174844417, 3),
+    (4611685021994975233, 5),
+    (4611684738527133697, 7),
+    (4611684691282493441, 3),
+    (4611684674102624257, 5),
+    (4611684609678114817, 5),
+    (4611684588203278337, 3),
+    (4611684274670665729, 7),
+    (4611684098577006593, 3),
+    (4611683789339361281, 3),
+    (4611683647605440513, 3),
+    (4611683643310473217, 7),
+];
+// every prime is above 2^61
+const PRIME_BITS: u64 = 61;
+// q - 1 is divisible by 2^32
+const MAX_LOG_LEN: u32 = 32;
+
+// Arithmetic mod one NTT prime in Montgomery form, R = 2^64. Values stay
+// below q < 2^62, so sums never overflow.
+struct Montgomery {
+    q: u64,
+    // -q^-1 mod 2^64
+    q_neg_inv: u64,
+    // R^2 mod q
+    r2: u64,
+}
+
+impl Montgomery {
+    fn new(q: u64) -> Self {
+        // Newton's iteration doubles the correct low bits of q^-1 each step
+        let mut inv = q;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(q.wrapping_mul(inv)));
+        }
+        let r = ((1u128 << 64) % q as u128) as u64;
+        Montgomery {
+            q,
+            q_neg_inv: inv.wrapping_neg(),
+            r2: ((r as u128 * r as u128) % q as u128) as u64,
+        }
+    }
+
+    // t R^-1 mod q for t < q 2^64
+    #[inline]
+    fn reduce(&self, t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(self.q_neg_inv);
+        let u = ((t + m as u128 * self.q as u128) >> 64) as u64;
+        self.reduce_once(u)
+    }
+
+    // u mod q for u < 2q, without a data-dependent branch
+    #[inline]
+    fn reduce_once(&self, u: u64) -> u64 {
+        let (v, borrow) = u.overflowing_sub(self.q);
+        v.wrapping_add(self.q & 0u64.wrapping_sub(borrow as u64))
+    }
+
+    #[inline]
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    #[inline]
+    fn add(&self, a: u64, b: u64) -> u64 {
+        self.reduce_once(a + b)
+    }
+
+    #[inline]
+    fn sub(&self, a: u64, b: u64) -> u64 {
+        let (v, borrow) = a.overflowing_sub(b);
+        v.wrapping_add(self.q & 0u64.wrapping_sub(borrow as u64))
+    }
+
+    fn to_mont(&self, a: u64) -> u64 {
+        self.mul(a % self.q, self.r2)
+    }
+
+    fn to_normal(&self, a: u64) -> u64 {
+        self.reduce(a as u128)
+    }
+
+    fn pow(&self, a: u64, mut e: u64) -> u64 {
+        let mut base = a;
+        let mut result = self.to_mont(1);
+        while e > 0 {
+            if e & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            e >>= 1;
+        }
+        result
+    }
+
+    // a mod q in Montgomery form, from little-endian limbs: the sum of
+    // limb_j R^(j + 1), with factors[j] = R^(j + 2) mod q. A limb may exceed
+    // q, the reduction only needs the product below q 2^64.
+    fn reduce_limbs(&self, limbs: &[u64], factors: &[u64]) -> u64 {
+        limbs.iter().zip(factors).fold(0, |acc, (&limb, &factor)| {
+            self.add(acc, self.mul(limb, factor))
+        })
+    }
+
+    // In-place cyclic NTT of length a.len(), a power of two, with the root
+    // of unity g^((q - 1) / len) or its inverse; the inverse transform is
+    // not scaled by 1 / len.
+    fn ntt(&self, a: &mut [u64], generator: u64, inverse: bool) {
+        let n = a.len();
+        let mut j = 0;
+        for i in 1..n {
+            let mut bit = n >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j |= bit;
+            if i < j {
+                a.swap(i, j);
+            }
+        }
+
+        let g = self.to_mont(generator);
+        let mut twiddles = Vec::with_capacity(n / 2);
+        let mut len = 2;
+        while len <= n {
+            let mut w = self.pow(g, (self.q - 1) / len as u64);
+            if inverse {
+                w = self.pow(w, self.q - 2);
+            }
+            twiddles.clear();
+            let mut wk = self.to_mont(1);
+            for _ in 0..len / 2 {
+                twiddles.push(wk);
+                wk = self.mul(wk, w);
+            }
+            for chunk in a.chunks_mut(len) {
+                let (low, high) = chunk.split_at_mut(len / 2);
+                for ((u, v), &wk) in low.iter_mut().zip(high.iter_mut()).zip(&twiddles) {
+                    let t = self.mul(*v, wk);
+                    *v = self.sub(*u, t);
+                    *u = self.add(*u, t);
+                }
+            }
+            len <<= 1;
+        }
+    }
+}
+
+// The coefficients of a b over F_p, or None when the product needs more
+// primes or a longer transform than the table offers.
+pub(crate) fn mul(a: &[BigUint], b: &[BigUint], p: &BigUint) -> Option<Vec<BigUint>> {
+    let count = a.len() + b.len() - 1;
+    let n = count.next_power_of_two();
+    let terms = a.len().min(b.len()) as u64;
+    let bound_bits = 2 * p.bits() + (64 - terms.leading_zeros() as u64) + 1;
+    let k = bound_bits.div_ceil(PRIME_BITS) as usize;
+    if k > PRIMES.len() || n.trailing_zeros() > MAX_LOG_LEN {
+        return None;
+    }
+
+    let square = std::ptr::eq(a, b);
+    let a: Vec<Vec<u64>> = a.iter().map(|c| c.to_u64_digits()).collect();
+    let b: Vec<Vec<u64>> = b.iter().map(|c| c.to_u64_digits()).collect();
+    let limbs = p.to_u64_digits().len();
+    let primes = &PRIMES[..k];
+    let fields: Vec<Montgomery> = primes.iter().map(|&(q, _)| Montgomery::new(q)).collect();
+
+    // the product's coefficients modulo each prime
+    let residues: Vec<Vec<u64>> = fields
+        .iter()
+        .zip(primes)
+        .map(|(field, &(_, generator))| {
+            let mut factors = vec![field.r2];
+            for _ in 1..limbs {
+                let next = field.mul(factors[factors.len() - 1], field.r2);
+                factors.push(next);
+            }
+            let transform = |coeffs: &[Vec<u64>]| {
+                let mut values = vec![0u64; n];
+                for (value, c) in values.iter_mut().zip(coeffs) {
+                    *value = field.reduce_limbs(c, &factors);
+                }
+                field.ntt(&mut values, generator, false);
+                values
+            };
+            let mut fa = transform(&a);
+            if square {
+                for v in fa.iter_mut() {
+                    *v = field.mul(*v, *v);
+                }
+            } else {
+                let fb = transform(&b);
+                for (u, v) in fa.iter_mut().zip(&fb) {
+                    *u = field.mul(*u, *v);
+                }
+            }
+            field.ntt(&mut fa, generator, true);
+            // multiplying by a plain 1 / n also leaves Montgomery form
+            let n_inv = field.to_normal(field.pow(field.to_mont(n as u64), field.q - 2));
+            fa.truncate(count);
+            fa.iter().map(|&v| field.mul(v, n_inv)).collect()
+        })
+        .collect();
+
+    // Garner: mixed-radix digits v_i with c = v_0 + v_1 Q_1 + v_2 Q_2 + ...,
+    // Q_i = q_0 ... q_(i - 1), then c mod p = sum of v_i (Q_i mod p)
+    let mut radix = vec![BigUint::from(1u32)];
+    for &(q, _) in &primes[..k - 1] {
+        let next = &radix[radix.len() - 1] * q;
+        radix.push(next);
+    }
+    // Q_t mod q_i in Montgomery form, so that mul(v_t, .) is v_t Q_t mod q_i
+    let radix_mont: Vec<Vec<u64>> = fields
+        .iter()
+        .map(|field| {
+            radix
+                .iter()
+                .map(|r| field.to_mont((r % field.q).iter_u64_digits().next().unwrap_or(0)))
+                .collect()
+        })
+        .collect();
+    // 1 / Q_i mod q_i in Montgomery form
+    let radix_inv: Vec<u64> = fields
+        .iter()
+        .zip(&radix_mont)
+        .enumerate()
+        .map(|(i, (field, radix))| field.pow(radix[i], field.q - 2))
+        .collect();
+    let radix_mod_p: Vec<Vec<u64>> = radix.iter().map(|r| (r % p).to_u64_digits()).collect();
+
+    let mut digits = vec![0u64; k];
+    let mut acc = vec![0u64; limbs + 2];
+    Some(
+        (0..count)
+            .map(|j| {
+                for (i, field) in fields.iter().enumerate() {
+                    let partial = (0..i).fold(0, |sum, t| {
+                        field.add(sum, field.mul(digits[t], radix_mont[i][t]))
+                    });
+                    let diff = field.sub(residues[i][j], partial);
+                    digits[i] = field.mul(diff, radix_inv[i]);
+                }
+                acc.iter_mut().for_each(|limb| *limb = 0);
+                for (&v, r) in digits.iter().zip(&radix_mod_p) {
+                    mul_add(&mut acc, r, v);
+                }
+                BigUint::from_slice(
+                    &acc.iter()
+                        .flat_map(|&l| [l as u32, (l >> 32) as u32])
+                        .collect::<Vec<u32>>(),
+                ) % p
+            })
+            .collect(),
+    )
+}
+
+// acc += r v
+fn mul_add(acc: &mut [u64], r: &[u64], v: u64) {
+    let mut carry = 0u128;
+    for (i, limb) in acc.iter_mut().enumerate() {
+        let product = r.get(i).map_or(0, |&r| r as u128 * v as u128);
+        let sum = *limb as u128 + product + carry;
+        *limb = sum as u64;
+        carry = sum >> 64;
+    }
+}
+
+#[cfg(test)]
+mod ntt_test {
+    use super::{mul, Montgomery, PRIMES};
+    use num_bigint::{BigUint, RandBigInt};
+
+    #[test]
+    fn test_primes() {
+        for &(q, g) in PRIMES.iter() {
+            assert!(crate::elliptic_curve::is_prime(&BigUint::from(q)));
+            assert_eq!((q - 1) % (1 << 32), 0);
+            // g has order q - 1: g^((q - 1) / 2) = -1
+            let field = Montgomery::new(q);
+            let half = field.pow(field.to_mont(g), (q - 1) / 2);
+            assert_eq!(field.to_normal(half), q - 1);
+        }
+    }
+
+    #[test]
+    fn test_matches_schoolbook() {
+        let mut rng = rand::thread_rng();
+        let p = BigUint::parse_bytes(
+            b"FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF",
+            16,
+        )
+        .expect("could not convert str to p");
+        for (m, n) in [(1, 1), (3, 70), (100, 100), (257, 31)] {
+            let a: Vec<BigUint> = (0..m).map(|_| rng.gen_biguint_below(&p)).collect();
+            let b: Vec<BigUint> = (0..n).map(|_| rng.gen_biguint_below(&p)).collect();
+            let mut expected = vec![BigUint::from(0u32); m + n - 1];
+            for (i, x) in a.iter().enumerate() {
+                for (j, y) in b.iter().enumerate() {
+                    expected[i + j] = (&expected[i + j] + x * y) % &p;
+                }
+            }
+            assert_eq!(mul(&a, &b, &p), Some(expected));
+            // squaring takes the single-transform path
+            let square = mul(&a, &a, &p).expect("fits the primes");
+            let mut expected = vec![BigUint::from(0u32); 2 * m - 1];
+            for (i, x) in a.iter().enumerate() {
+                for (j, y) in a.iter().enumerate() {
+                    expected[i + j] = (&expected[i + j] + x * y) % &p;
+                }
+            }
+            assert_eq!(square, expected);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use super::elliptic_curve::{EllipticCurve, Point};
+use super::jacobian::JacobianPoint;
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Bucket width for n terms. Each window costs about n + 2^(c+1) additions
+// and there are bits / c windows, which is smallest near c = ln(n).
+pub fn pippenger_window(n: usize) -> usize {
+    if n < 4 {
+        1
+    } else {
+        (n.ilog2() as usize * 69 / 100 + 2).min(16)
+    }
+}
+
+// the c-bit digit of k starting at bit `start`
+fn window_digit(k: &BigUint, start: u64, c: usize) -> usize {
+    (0..c as u64).fold(0, |acc, b| acc | ((k.bit(start + b) as usize) << b))
+}
+
+impl EllipticCurve {
+    pub fn pippenger_mul(&self, terms: &[(Point, BigUint)]) -> Point {
+        self.try_pippenger_mul(terms)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // Pippenger's bucket method for k1 P1 + k2 P2 + ... with many terms.
+    // Scalars are cut into c-bit windows; in each window every point goes
+    // into the bucket of its digit, and the buckets are summed with a running
+    // sum so that bucket d counts d times. Variable time, public scalars only.
+    // With the "parallel" feature the windows are processed on the rayon pool.
+    pub fn try_pippenger_mul(&self, terms: &[(Point, BigUint)]) -> Result<Point> {
+        if let Some((c, _)) = terms.iter().find(|(c, _)| !self.is_on_curve(c)) {
+            return Err(EccError::NotOnCurve(c.clone()));
+        }
+
+        let c = pippenger_window(terms.len());
+        let bits = terms.iter().map(|(_, k)| k.bits()).max().unwrap_or(0);
+        let windows = bits.div_ceil(c as u64) as usize;
+
+        #[cfg(feature = "parallel")]
+        let sums: Vec<JacobianPoint> = (0..windows)
+            .into_par_iter()
+            .map(|j| self.window_sum(terms, j, c))
+            .collect();
+        #[cfg(not(feature = "parallel"))]
+        let sums: Vec<JacobianPoint> = (0..windows).map(|j| self.window_sum(terms, j, c)).collect();
+
+        // combine from the most significant window down
+        let mut acc = self.jacobian_identity();
+        for sum in sums.iter().rev() {
+            for _ in 0..c {
+                acc = self.jacobian_double(&acc);
+            }
+            acc = self.jacobian_add(&acc, sum);
+        }
+        self.try_to_affine(&acc)
+    }
+
+    // sum over the terms of digit_j(k) * P for window j
+    fn window_sum(&self, terms: &[(Point, BigUint)], j: usize, c: usize) -> JacobianPoint<'_> {
+        let start = (j * c) as u64;
+        let mut buckets = vec![self.jacobian_identity(); (1 << c) - 1];
+        for (point, k) in terms {
+            let digit = window_digit(k, start, c);
+            if digit != 0 {
+                buckets[digit - 1] = self.jacobian_add_mixed(&buckets[digit - 1], point);
+            }
+        }
+
+        // running = B_d + ... + B_top, so sum = sum over d of d * B_d
+        let mut running = self.jacobian_identity();
+        let mut sum = self.jacobian_identity();
+        for bucket in buckets.iter().rev() {
+            running = self.jacobian_add(&running, bucket);
+            sum = self.jacobian_add(&sum, &running);
+        }
+        sum
+    }
+}
+
+#[cfg(test)]
+mod pippenger_test {
+    use super::{pippenger_window, BigUint, EccError, EllipticCurve, Point};
+    use crate::curves;
+    use rand::Rng;
+
+    #[test]
+    fn test_window_grows_with_input() {
+        assert_eq!(pippenger_window(0), 1);
+        assert_eq!(pippenger_window(1), 1);
+        let widths: Vec<usize> = (2..20).map(|i| pippenger_window(1 << i)).collect();
+        assert!(widths.windows(2).all(|w| w[0] <= w[1]));
+        assert!(widths.iter().all(|&c| c <= 16));
+    }
+
+    #[test]
+    fn test_matches_naive_sum() {
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+        let g = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
+        let mut rng = rand::thread_rng();
+
+        for n in [0, 1, 2, 5, 40, 300] {
+            let terms: Vec<(Point, BigUint)> = (0..n)
+                .map(|_| {
+                    let point = ec.scalar_mul(&g, &BigUint::from(rng.gen_range(0..19u32)));
+                    (point, BigUint::from(rng.gen_range(0..1000u32)))
+                })
+                .collect();
+            let expected = terms.iter().fold(Point::Identity, |acc, (c, k)| {
+                ec.add(&acc, &ec.scalar_mul(c, k))
+            });
+            assert_eq!(ec.pippenger_mul(&terms), expected);
+        }
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let params = curves::secp256k1();
+        let (ec, g) = (params.curve(), params.generator());
+        let mut rng = rand::thread_rng();
+
+        let terms: Vec<(Point, BigUint)> = (1..=64u32)
+            .map(|i| {
+                let point = ec.scalar_mul(g, &BigUint::from(i));
+                let k = BigUint::from_bytes_be(&rng.gen::<[u8; 32]>()) % params.order();
+                (point, k)
+            })
+            .collect();
+        let expected = terms.iter().fold(Point::Identity, |acc, (c, k)| {
+            ec.add(&acc, &ec.scalar_mul(c, k))
+        });
+        assert_eq!(ec.pippenger_mul(&terms), expected);
+        assert_eq!(ec.multi_scalar_mul(&terms), expected);
+    }
+
+    #[test]
+    fn test_not_on_curve() {
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+        let off_curve = Point::Coordinates(BigUint::from(5u32), BigUint::from(2u32));
+        assert_eq!(
+            ec.try_pippenger_mul(&[(off_curve.clone(), BigUint::from(1u32))]),
+            Err(EccError::NotOnCurve(off_curve))
+        );
+    }
+}
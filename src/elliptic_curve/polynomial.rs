@@ -0,0 +1,463 @@
+use super::ntt;
+use num_bigint::BigUint;
+use num_integer::Roots;
+use num_traits::{One, Zero};
+
+// below this many coefficients schoolbook multiplication beats packing
+const KRONECKER_THRESHOLD: usize = 16;
+// and from this many on, transforms modulo word-sized primes beat both
+const NTT_THRESHOLD: usize = 64;
+
+// Dense polynomial over F_p, lowest degree first, coefficients reduced
+// mod p and no trailing zeros, so the zero polynomial is empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Poly {
+    coeffs: Vec<BigUint>,
+}
+
+impl Poly {
+    pub(crate) fn zero() -> Self {
+        Poly { coeffs: Vec::new() }
+    }
+
+    // coefficients must already be reduced mod p
+    pub(crate) fn new(coeffs: Vec<BigUint>) -> Self {
+        let mut poly = Poly { coeffs };
+        poly.trim();
+        poly
+    }
+
+    pub(crate) fn constant(c: BigUint) -> Self {
+        Self::new(vec![c])
+    }
+
+    pub(crate) fn x() -> Self {
+        Self::new(vec![BigUint::zero(), BigUint::one()])
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    // degree 0 for non-zero constants and for zero
+    pub(crate) fn degree(&self) -> usize {
+        self.coeffs.len().saturating_sub(1)
+    }
+
+    fn leading(&self) -> Option<&BigUint> {
+        self.coeffs.last()
+    }
+
+    fn trim(&mut self) {
+        while self.coeffs.last().is_some_and(|c| c.is_zero()) {
+            self.coeffs.pop();
+        }
+    }
+}
+
+// Arithmetic in F_p[x].
+pub(crate) struct PolyRing<'a> {
+    p: &'a BigUint,
+}
+
+impl<'a> PolyRing<'a> {
+    pub(crate) fn new(p: &'a BigUint) -> Self {
+        PolyRing { p }
+    }
+
+    pub(crate) fn add(&self, a: &Poly, b: &Poly) -> Poly {
+        let n = a.coeffs.len().max(b.coeffs.len());
+        let zero = BigUint::zero();
+        let coeffs = (0..n)
+            .map(|i| {
+                let sum = a.coeffs.get(i).unwrap_or(&zero) + b.coeffs.get(i).unwrap_or(&zero);
+                if &sum >= self.p {
+                    sum - self.p
+                } else {
+                    sum
+                }
+            })
+            .collect();
+        Poly::new(coeffs)
+    }
+
+    pub(crate) fn neg(&self, a: &Poly) -> Poly {
+        Poly::new(
+            a.coeffs
+                .iter()
+                .map(|c| if c.is_zero() { c.clone() } else { self.p - c })
+                .collect(),
+        )
+    }
+
+    pub(crate) fn sub(&self, a: &Poly, b: &Poly) -> Poly {
+        self.add(a, &self.neg(b))
+    }
+
+    pub(crate) fn scale(&self, a: &Poly, c: &BigUint) -> Poly {
+        Poly::new(a.coeffs.iter().map(|x| x * c % self.p).collect())
+    }
+
+    pub(crate) fn mul(&self, a: &Poly, b: &Poly) -> Poly {
+        if a.is_zero() || b.is_zero() {
+            return Poly::zero();
+        }
+        let terms = a.coeffs.len().min(b.coeffs.len());
+        if terms < KRONECKER_THRESHOLD {
+            return self.mul_schoolbook(a, b);
+        }
+        if terms >= NTT_THRESHOLD {
+            if let Some(coeffs) = ntt::mul(&a.coeffs, &b.coeffs, self.p) {
+                return Poly::new(coeffs);
+            }
+        }
+        self.mul_kronecker(a, b)
+    }
+
+    fn mul_schoolbook(&self, a: &Poly, b: &Poly) -> Poly {
+        let mut coeffs = vec![BigUint::zero(); a.coeffs.len() + b.coeffs.len() - 1];
+        for (i, x) in a.coeffs.iter().enumerate() {
+            for (j, y) in b.coeffs.iter().enumerate() {
+                coeffs[i + j] += x * y;
+            }
+        }
+        Poly::new(coeffs.into_iter().map(|c| c % self.p).collect())
+    }
+
+    // Kronecker substitution: evaluate both polynomials at 2^slot, multiply
+    // the two integers and read the product's coefficients back out. The
+    // slot is wide enough that no coefficient of the product overflows, so
+    // one big multiplication replaces len(a) * len(b) small ones.
+    fn mul_kronecker(&self, a: &Poly, b: &Poly) -> Poly {
+        let terms = a.coeffs.len().min(b.coeffs.len()) as u64;
+        let slot = 2 * self.p.bits() + (64 - terms.leading_zeros() as u64) + 1;
+        let product = pack(&a.coeffs, slot) * pack(&b.coeffs, slot);
+        let count = a.coeffs.len() + b.coeffs.len() - 1;
+        let limbs = product.to_u64_digits();
+        Poly::new(
+            (0..count)
+                .map(|i| extract(&limbs, i as u64 * slot, slot) % self.p)
+                .collect(),
+        )
+    }
+
+    // a = q b + r with deg r < deg b; b must be non-zero
+    pub(crate) fn div_rem(&self, a: &Poly, b: &Poly) -> (Poly, Poly) {
+        let lead_inv = self.inv_scalar(b.leading().expect("division by the zero polynomial"));
+        let mut r = a.coeffs.clone();
+        let db = b.coeffs.len() - 1;
+        if r.len() <= db {
+            return (Poly::zero(), a.clone());
+        }
+        let mut q = vec![BigUint::zero(); r.len() - db];
+        for i in (0..q.len()).rev() {
+            let c = &r[i + db] * &lead_inv % self.p;
+            if c.is_zero() {
+                continue;
+            }
+            for (j, bj) in b.coeffs.iter().enumerate() {
+                // r[i + j] -= c * b[j]
+                let t = &c * bj % self.p;
+                r[i + j] = (&r[i + j] + self.p - t) % self.p;
+            }
+            q[i] = c;
+        }
+        r.truncate(db);
+        (Poly::new(q), Poly::new(r))
+    }
+
+    pub(crate) fn rem(&self, a: &Poly, b: &Poly) -> Poly {
+        self.div_rem(a, b).1
+    }
+
+    pub(crate) fn monic(&self, a: &Poly) -> Poly {
+        match a.leading() {
+            Some(lead) => self.scale(a, &self.inv_scalar(lead)),
+            None => Poly::zero(),
+        }
+    }
+
+    // monic gcd
+    pub(crate) fn gcd(&self, a: &Poly, b: &Poly) -> Poly {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        while !b.is_zero() {
+            let r = self.rem(&a, &b);
+            a = b;
+            b = r;
+        }
+        self.monic(&a)
+    }
+
+    // (g, s) with g = gcd(a, m) monic and s a = g (mod m)
+    pub(crate) fn gcd_inverse(&self, a: &Poly, m: &Poly) -> (Poly, Poly) {
+        let (mut r0, mut r1) = (m.clone(), self.rem(a, m));
+        let (mut s0, mut s1) = (Poly::zero(), Poly::constant(BigUint::one()));
+        while !r1.is_zero() {
+            let (q, r) = self.div_rem(&r0, &r1);
+            let s = self.sub(&s0, &self.mul(&q, &s1));
+            r0 = std::mem::replace(&mut r1, r);
+            s0 = std::mem::replace(&mut s1, s);
+        }
+        match r0.leading() {
+            Some(lead) => {
+                let lead_inv = self.inv_scalar(lead);
+                (
+                    self.scale(&r0, &lead_inv),
+                    self.rem(&self.scale(&s0, &lead_inv), m),
+                )
+            }
+            // a = 0 (mod m)
+            None => (self.monic(m), Poly::zero()),
+        }
+    }
+
+    pub(crate) fn inv_scalar(&self, c: &BigUint) -> BigUint {
+        c.modpow(&(self.p - 2u32), self.p)
+    }
+
+    // 1 / h mod x^k for h(0) != 0, by Newton iteration g <- g (2 - h g)
+    fn series_inv(&self, h: &[BigUint], k: usize) -> Vec<BigUint> {
+        let mut g = Poly::constant(self.inv_scalar(&h[0]));
+        let mut precision = 1;
+        let two = Poly::constant(BigUint::from(2u32) % self.p);
+        while precision < k {
+            precision = (2 * precision).min(k);
+            let h = Poly::new(h.iter().take(precision).cloned().collect());
+            let hg = truncate(&self.mul(&h, &g), precision);
+            g = truncate(&self.mul(&g, &self.sub(&two, &hg)), precision);
+        }
+        let mut coeffs = g.coeffs;
+        coeffs.resize(k, BigUint::zero());
+        coeffs
+    }
+}
+
+// F_p[x] / (m) for a monic m of degree d >= 1, with products reduced by
+// a precomputed inverse of reversed m (Barrett for polynomials): two
+// multiplications instead of a long division.
+pub(crate) struct PolyModulus<'a> {
+    ring: PolyRing<'a>,
+    m: Poly,
+    // 1 / rev(m) mod x^(d - 1)
+    inv_rev: Vec<BigUint>,
+}
+
+impl<'a> PolyModulus<'a> {
+    pub(crate) fn new(ring: PolyRing<'a>, m: &Poly) -> Self {
+        let m = ring.monic(m);
+        let d = m.degree();
+        assert!(d >= 1, "modulus must have positive degree");
+        let rev: Vec<BigUint> = m.coeffs.iter().rev().cloned().collect();
+        let inv_rev = if d > 1 {
+            ring.series_inv(&rev, d - 1)
+        } else {
+            Vec::new()
+        };
+        PolyModulus { ring, m, inv_rev }
+    }
+
+    pub(crate) fn ring(&self) -> &PolyRing<'a> {
+        &self.ring
+    }
+
+    pub(crate) fn modulus(&self) -> &Poly {
+        &self.m
+    }
+
+    // a mod m for deg a <= 2d - 2, e.g. a product of two reduced polynomials
+    pub(crate) fn reduce(&self, a: &Poly) -> Poly {
+        let d = self.m.degree();
+        if a.coeffs.len() <= d {
+            return a.clone();
+        }
+        if a.coeffs.len() > 2 * d - 1 {
+            return self.ring.rem(a, &self.m);
+        }
+        // quotient has k coefficients: rev(q) = rev(a) / rev(m) mod x^k
+        let k = a.coeffs.len() - d;
+        let rev_a = Poly::new(a.coeffs.iter().rev().take(k).cloned().collect());
+        let inv = Poly::new(self.inv_rev.iter().take(k).cloned().collect());
+        let mut q_rev = truncate(&self.ring.mul(&rev_a, &inv), k).coeffs;
+        q_rev.resize(k, BigUint::zero());
+        let q = Poly::new(q_rev.into_iter().rev().collect());
+        let r = self.ring.sub(a, &self.ring.mul(&q, &self.m));
+        truncate(&r, d)
+    }
+
+    pub(crate) fn mul(&self, a: &Poly, b: &Poly) -> Poly {
+        self.reduce(&self.ring.mul(a, b))
+    }
+
+    pub(crate) fn pow(&self, a: &Poly, e: &BigUint) -> Poly {
+        let mut result = self.reduce(&Poly::constant(BigUint::one()));
+        let a = self.reduce(a);
+        for i in (0..e.bits()).rev() {
+            result = self.mul(&result, &result);
+            if e.bit(i) {
+                result = self.mul(&result, &a);
+            }
+        }
+        result
+    }
+
+    // g(h) mod m for each g, by Brent-Kung: with k about sqrt(d), the powers
+    // h^0 .. h^(k - 1) turn each block of k coefficients of g into a linear
+    // combination, and the blocks are joined by Horner's rule in h^k. That
+    // is about 2 sqrt(d) products per g instead of d.
+    pub(crate) fn compose(&self, gs: &[&Poly], h: &Poly) -> Vec<Poly> {
+        let d = self.m.degree();
+        let k = d.sqrt() + 1;
+        let h = self.reduce(h);
+        let mut powers = vec![self.reduce(&Poly::constant(BigUint::one()))];
+        for i in 0..k {
+            let next = self.mul(&powers[i], &h);
+            powers.push(next);
+        }
+        let hk = powers.pop().expect("k + 1 powers");
+        gs.iter()
+            .map(|g| {
+                let blocks: Vec<Poly> = g
+                    .coeffs
+                    .chunks(k)
+                    .map(|block| self.combine(block, &powers))
+                    .collect();
+                blocks.iter().rev().fold(Poly::zero(), |acc, block| {
+                    self.ring.add(&self.mul(&acc, &hk), block)
+                })
+            })
+            .collect()
+    }
+
+    // sum of c_i powers_i, each coefficient reduced once at the end
+    fn combine(&self, coeffs: &[BigUint], powers: &[Poly]) -> Poly {
+        let mut acc = vec![BigUint::zero(); self.m.degree()];
+        for (c, power) in coeffs.iter().zip(powers) {
+            if c.is_zero() {
+                continue;
+            }
+            for (a, x) in acc.iter_mut().zip(&power.coeffs) {
+                *a += c * x;
+            }
+        }
+        Poly::new(acc.into_iter().map(|a| a % self.ring.p).collect())
+    }
+
+    // (gcd(a, m), a^-1 mod m when that gcd is 1)
+    pub(crate) fn gcd_inverse(&self, a: &Poly) -> (Poly, Poly) {
+        self.ring.gcd_inverse(a, &self.m)
+    }
+}
+
+// a mod x^k
+fn truncate(a: &Poly, k: usize) -> Poly {
+    Poly::new(a.coeffs.iter().take(k).cloned().collect())
+}
+
+// sum of coeffs[i] 2^(slot i); every coefficient fits in a slot
+fn pack(coeffs: &[BigUint], slot: u64) -> BigUint {
+    let mut limbs = vec![0u64; (coeffs.len() as u64 * slot).div_ceil(64) as usize + 1];
+    for (i, c) in coeffs.iter().enumerate() {
+        let start = i as u64 * slot;
+        for (j, digit) in c.iter_u64_digits().enumerate() {
+            let bit = start + 64 * j as u64;
+            let (word, shift) = ((bit / 64) as usize, bit % 64);
+            limbs[word] |= digit << shift;
+            if shift > 0 {
+                limbs[word + 1] |= digit >> (64 - shift);
+            }
+        }
+    }
+    BigUint::from_slice(
+        &limbs
+            .iter()
+            .flat_map(|&l| [l as u32, (l >> 32) as u32])
+            .collect::<Vec<u32>>(),
+    )
+}
+
+// bits [start, start + len) of the little-endian limbs
+fn extract(limbs: &[u64], start: u64, len: u64) -> BigUint {
+    let words = len.div_ceil(64) as usize;
+    let (first, shift) = ((start / 64) as usize, start % 64);
+    let get = |i: usize| limbs.get(i).copied().unwrap_or(0);
+    let mut out: Vec<u64> = (0..words)
+        .map(|i| {
+            let low = get(first + i) >> shift;
+            let high = if shift > 0 {
+                get(first + i + 1) << (64 - shift)
+            } else {
+                0
+            };
+            low | high
+        })
+        .collect();
+    let extra = words as u64 * 64 - len;
+    if extra > 0 {
+        if let Some(top) = out.last_mut() {
+            *top &= u64::MAX >> extra;
+        }
+    }
+    BigUint::from_slice(
+        &out.iter()
+            .flat_map(|&l| [l as u32, (l >> 32) as u32])
+            .collect::<Vec<u32>>(),
+    )
+}
+
+#[cfg(test)]
+mod polynomial_test {
+    use super::{BigUint, Poly, PolyModulus, PolyRing};
+    use num_bigint::RandBigInt;
+
+    fn random_poly(p: &BigUint, len: usize) -> Poly {
+        let mut rng = rand::thread_rng();
+        Poly::new((0..len).map(|_| rng.gen_biguint_below(p)).collect())
+    }
+
+    #[test]
+    fn test_kronecker_matches_schoolbook() {
+        let p = BigUint::parse_bytes(
+            b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .expect("could not convert str to p");
+        let ring = PolyRing::new(&p);
+        for (m, n) in [(20, 20), (17, 50), (64, 33)] {
+            let a = random_poly(&p, m);
+            let b = random_poly(&p, n);
+            assert_eq!(ring.mul_kronecker(&a, &b), ring.mul_schoolbook(&a, &b));
+        }
+    }
+
+    #[test]
+    fn test_reduce_and_inverse() {
+        let p = BigUint::from(1_000_003u32);
+        let ring = PolyRing::new(&p);
+        let m = random_poly(&p, 40);
+        let modulus = PolyModulus::new(PolyRing::new(&p), &m);
+        let a = random_poly(&p, 39);
+        let b = random_poly(&p, 39);
+        let product = ring.mul(&a, &b);
+        assert_eq!(modulus.mul(&a, &b), ring.rem(&product, modulus.modulus()));
+
+        // composing with x^p is raising to the p-th power
+        let xp = modulus.pow(&Poly::x(), &p);
+        let composed = modulus.compose(&[&a, &b], &xp);
+        assert_eq!(composed[0], modulus.pow(&a, &p));
+        assert_eq!(composed[1], modulus.pow(&b, &p));
+
+        let (g, inv) = modulus.gcd_inverse(&a);
+        if g == Poly::constant(BigUint::from(1u32)) {
+            assert_eq!(modulus.mul(&inv, &a), Poly::constant(BigUint::from(1u32)));
+        }
+
+        // x^p = -x in F_p[x] / (x^2 + 1) since p = 3 mod 4
+        let m = Poly::new(vec![
+            BigUint::from(1u32),
+            BigUint::from(0u32),
+            BigUint::from(1u32),
+        ]);
+        let modulus = PolyModulus::new(PolyRing::new(&p), &m);
+        assert_eq!(modulus.pow(&Poly::x(), &p), ring.neg(&Poly::x()));
+    }
+}
@@ -0,0 +1,116 @@
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::Zero;
+use rand::thread_rng;
+
+const SMALL_PRIMES: [u32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+// Random Miller-Rabin rounds on top of the fixed small-prime bases,
+// giving an error probability below 4^(-40) for large inputs.
+const RANDOM_ROUNDS: usize = 40;
+
+// Miller-Rabin probabilistic primality test.
+// The small-prime bases alone are deterministic for n < 3.3 * 10^24.
+pub fn is_prime(n: &BigUint) -> bool {
+    let one = BigUint::from(1u32);
+    let two = BigUint::from(2u32);
+
+    if n < &two {
+        return false;
+    }
+    for p in SMALL_PRIMES {
+        let p = BigUint::from(p);
+        if n == &p {
+            return true;
+        }
+        if (n % &p) == BigUint::from(0u32) {
+            return false;
+        }
+    }
+
+    // n - 1 = d * 2^s, d odd
+    let n_minus_one = n - &one;
+    let s = n_minus_one
+        .trailing_zeros()
+        .expect("n - 1 is non-zero for n > 2");
+    let d = &n_minus_one >> s;
+
+    let is_witness = |a: &BigUint| -> bool {
+        let mut x = a.modpow(&d, n);
+        if x == one || x == n_minus_one {
+            return false;
+        }
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+            if x == n_minus_one {
+                return false;
+            }
+        }
+        true
+    };
+
+    if SMALL_PRIMES.iter().any(|&a| is_witness(&BigUint::from(a))) {
+        return false;
+    }
+
+    let mut rng = thread_rng();
+    (0..RANDOM_ROUNDS).all(|_| !is_witness(&rng.gen_biguint_range(&two, &n_minus_one)))
+}
+
+// [(p, e), ...] by trial division, for small n such as curve cofactors
+pub(crate) fn trial_division(n: &BigUint) -> Vec<(BigUint, u32)> {
+    let mut n = n.clone();
+    let mut factors = Vec::new();
+    let mut d = BigUint::from(2u32);
+    while &d * &d <= n {
+        let mut e = 0;
+        while (&n % &d).is_zero() {
+            n /= &d;
+            e += 1;
+        }
+        if e > 0 {
+            factors.push((d.clone(), e));
+        }
+        d += 1u32;
+    }
+    if n > BigUint::from(1u32) {
+        factors.push((n, 1));
+    }
+    factors
+}
+
+#[cfg(test)]
+mod primality_test {
+    use super::{is_prime, BigUint};
+
+    #[test]
+    fn test_small_numbers() {
+        let primes: Vec<u32> = (0..100u32)
+            .filter(|n| is_prime(&BigUint::from(*n)))
+            .collect();
+        assert_eq!(
+            primes,
+            vec![
+                2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79,
+                83, 89, 97
+            ]
+        );
+    }
+
+    #[test]
+    fn test_carmichael_numbers() {
+        for n in [561u32, 1105, 1729, 2465, 2821, 6601, 8911] {
+            assert!(!is_prime(&BigUint::from(n)), "{} is a Carmichael number", n);
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_order() {
+        let n = BigUint::parse_bytes(
+            b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+            16,
+        )
+        .expect("could not convert str to n");
+        assert!(is_prime(&n));
+        assert!(!is_prime(&(&n * &n)));
+    }
+}
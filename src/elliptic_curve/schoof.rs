@@ -0,0 +1,548 @@
+use super::domain_parameters::within_hasse_bound;
+use super::elliptic_curve::{EllipticCurve, Point};
+use super::polynomial::{Poly, PolyModulus, PolyRing};
+use crate::error::{EccError, Result};
+use num_bigint::{BigInt, BigUint, RandBigInt};
+use num_integer::{Integer, Roots};
+use num_traits::{One, ToPrimitive, Zero};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+// Schoof stops once at most 2^SCHOOF_BSGS_BITS traces are left and
+// baby-step giant-step picks the right one, which is far cheaper than the
+// division polynomials of the last few primes.
+const SCHOOF_BSGS_BITS: u64 = 40;
+// random points tried before giving up on baby-step giant-step
+const BSGS_ATTEMPTS: usize = 8;
+// points normalized per shared inversion during the walks
+const BSGS_BATCH: usize = 512;
+
+impl EllipticCurve {
+    pub fn count_points_schoof(&self) -> BigUint {
+        self.try_count_points_schoof()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // #E = p + 1 - t. Schoof's algorithm finds the Frobenius trace t modulo
+    // small primes l from the action of Frobenius on the l-torsion, working
+    // modulo the l-th division polynomial, and the CRT combines them. The
+    // last candidates in the Hasse interval are told apart by baby-step
+    // giant-step on random points.
+    pub fn try_count_points_schoof(&self) -> Result<BigUint> {
+        self.schoof_count(SCHOOF_BSGS_BITS)
+    }
+
+    // bsgs_bits = 0 runs Schoof until t is unique in the Hasse interval
+    fn schoof_count(&self, bsgs_bits: u64) -> Result<BigUint> {
+        self.validate()?;
+        let p = self.p();
+        if p <= &BigUint::from(3u32) {
+            return self.try_count_points();
+        }
+
+        // |t| <= 2 sqrt(p)
+        let bound = BigInt::from((p << 2u32).sqrt());
+        let span = BigUint::try_from(&bound * 2 + 1).expect("the bound is non-negative");
+        let mut primes = Vec::new();
+        let mut modulus = BigUint::one();
+        let mut l = 1;
+        while (&modulus << bsgs_bits) < span {
+            l = next_prime(l);
+            if p != &BigUint::from(l) {
+                primes.push(l);
+                modulus *= l;
+            }
+        }
+
+        let schoof = Schoof::new(self, primes.last().copied().unwrap_or(2));
+        let residues: Vec<(u64, u64)> = primes.iter().map(|&l| (l, schoof.trace_mod(l))).collect();
+        let modulus = BigInt::from(modulus);
+
+        // the traces left are start, start + M, ... up to the bound
+        let start = -&bound + (BigInt::from(crt(&residues)) + &bound).mod_floor(&modulus);
+        let count = ((&bound - &start) / &modulus + 1u32)
+            .to_u64()
+            .expect("at most 2^bsgs_bits candidates");
+        let trace = if count == 1 {
+            start
+        } else {
+            match self.bsgs_trace(&start, &modulus, count)? {
+                Some(trace) => trace,
+                // every point found has a small order: finish with Schoof
+                None => return self.schoof_count(0),
+            }
+        };
+
+        let order = BigUint::try_from(BigInt::from(p.clone()) + 1u32 - trace)
+            .expect("p + 1 - t is positive");
+        if !within_hasse_bound(p, &order) {
+            return Err(EccError::HasseBoundViolated);
+        }
+        Ok(order)
+    }
+
+    // The trace among start + kM, 0 <= k < count: (p + 1 - start - kM) c
+    // is the identity, that is (p + 1 - start) c = k (M c). Each random point
+    // narrows the k that fit, None if they never narrow to one.
+    fn bsgs_trace(&self, start: &BigInt, m: &BigInt, count: u64) -> Result<Option<BigInt>> {
+        let n0 = BigUint::try_from(BigInt::from(self.p().clone()) + 1u32 - start)
+            .expect("p + 1 - t is positive");
+        let m_unsigned = BigUint::try_from(m).expect("the modulus is positive");
+        let mut candidates: Option<Vec<u64>> = None;
+        for _ in 0..BSGS_ATTEMPTS {
+            let c = self.random_point();
+            let s = self.try_scalar_mul(&c, &m_unsigned)?;
+            let r = self.try_scalar_mul(&c, &n0)?;
+            let found = match self.bsgs(&r, &s, count)? {
+                Some(found) => found,
+                None => continue,
+            };
+            let narrowed = match candidates {
+                Some(previous) => previous.into_iter().filter(|k| found.contains(k)).collect(),
+                None => found,
+            };
+            if let [k] = narrowed[..] {
+                return Ok(Some(start + m * k));
+            }
+            candidates = Some(narrowed);
+        }
+        Ok(None)
+    }
+
+    // Every k in [0, count) with k s = r, using ceil(sqrt(count)) baby steps
+    // j s and giant steps r - i m s. None when s has order below m, where the
+    // baby steps would repeat.
+    fn bsgs(&self, r: &Point, s: &Point, count: u64) -> Result<Option<Vec<u64>>> {
+        let mut m = count.sqrt();
+        if m * m < count {
+            m += 1;
+        }
+
+        let mut baby = HashMap::with_capacity(m as usize);
+        let mut repeats = false;
+        self.walk(&Point::Identity, s, m, |j, c| {
+            repeats |= j > 0 && c == &Point::Identity;
+            baby.entry(point_hash(c)).or_insert(j);
+        })?;
+        if repeats {
+            return Ok(None);
+        }
+
+        let giant = self.negate(&self.try_scalar_mul(s, &BigUint::from(m))?);
+        let mut found = Vec::new();
+        self.walk(r, &giant, count.div_ceil(m), |i, c| {
+            if let Some(j) = baby.get(&point_hash(c)) {
+                let k = i * m + j;
+                if k < count {
+                    found.push(k);
+                }
+            }
+        })?;
+        Ok(Some(found))
+    }
+
+    // visits start + i step for 0 <= i < len, normalizing in batches
+    fn walk(
+        &self,
+        start: &Point,
+        step: &Point,
+        len: u64,
+        mut visit: impl FnMut(u64, &Point),
+    ) -> Result<()> {
+        let mut acc = self.to_jacobian(start);
+        let mut i = 0;
+        while i < len {
+            let mut batch = Vec::with_capacity(BSGS_BATCH);
+            while batch.len() < BSGS_BATCH && i + (batch.len() as u64) < len {
+                let next = self.jacobian_add_mixed(&acc, step);
+                batch.push(std::mem::replace(&mut acc, next));
+            }
+            for c in self.batch_to_affine(&batch)? {
+                visit(i, &c);
+                i += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn random_point(&self) -> Point {
+        let mut rng = rand::thread_rng();
+        loop {
+            let x = rng.gen_biguint_below(self.p());
+            if let Some((y, _)) = self.field().sqrt(&self.rhs(&x)) {
+                return Point::Coordinates(x, y);
+            }
+        }
+    }
+}
+
+// The polynomial arithmetic behind Schoof's algorithm for one curve.
+struct Schoof<'a> {
+    p: &'a BigUint,
+    a: BigUint,
+    b: BigUint,
+    // x^3 + ax + b
+    f: Poly,
+    // g_0, g_1, ... as exact polynomials
+    g: Vec<Poly>,
+}
+
+// Division polynomials kept as psi_n = g_n for odd n and y g_n for even n,
+// so every g_n is a polynomial in x alone with y^2 replaced by f. They are
+// taken at a point x of some ring, with f = x^3 + ax + b at the same point,
+// and extended on demand by the usual doubling recurrences.
+struct DivisionPolynomials<'a, 'm> {
+    ring: PolyRing<'a>,
+    mul: &'m dyn Fn(&Poly, &Poly) -> Poly,
+    f2: Poly,
+    half: BigUint,
+    g: Vec<Poly>,
+    // g_n^2, which the recurrences and the x-coordinates of [n] share
+    squares: Vec<Poly>,
+}
+
+impl<'a, 'm> DivisionPolynomials<'a, 'm> {
+    fn new(schoof: &Schoof<'a>, x: &Poly, f: &Poly, mul: &'m dyn Fn(&Poly, &Poly) -> Poly) -> Self {
+        let ring = schoof.ring();
+        let p = schoof.p;
+        let neg = |c: BigUint| (p - c % p) % p;
+        let (a, b) = (&schoof.a, &schoof.b);
+        let x2 = mul(x, x);
+        let x3 = mul(&x2, x);
+        let x4 = mul(&x2, &x2);
+        let x6 = mul(&x3, &x3);
+        let sum = |terms: &[(BigUint, &Poly)], c: BigUint| {
+            terms.iter().fold(schoof.constant(c), |acc, (k, u)| {
+                ring.add(&acc, &ring.scale(u, &(k % p)))
+            })
+        };
+
+        // 3x^4 + 6ax^2 + 12bx - a^2
+        let g3 = sum(
+            &[(BigUint::from(3u32), &x4), (a * 6u32, &x2), (b * 12u32, x)],
+            neg(a * a),
+        );
+        // 4 (x^6 + 5ax^4 + 20bx^3 - 5a^2x^2 - 4abx - 8b^2 - a^3)
+        let g4 = sum(
+            &[
+                (BigUint::from(4u32), &x6),
+                (a * 20u32, &x4),
+                (b * 80u32, &x3),
+                (neg(a * a * 20u32), &x2),
+                (neg(a * b * 16u32), x),
+            ],
+            neg(b * b * 32u32 + a * a * a * 4u32),
+        );
+
+        let g = vec![
+            Poly::zero(),
+            schoof.constant(1u32),
+            schoof.constant(2u32),
+            g3,
+            g4,
+        ];
+        DivisionPolynomials {
+            f2: mul(f, f),
+            half: ring.inv_scalar(&BigUint::from(2u32)),
+            squares: g.iter().map(|u| mul(u, u)).collect(),
+            ring,
+            mul,
+            g,
+        }
+    }
+
+    // g_0 .. g_n are available afterwards
+    fn extend_to(&mut self, n: usize) {
+        let (ring, mul) = (&self.ring, self.mul);
+        while self.g.len() <= n {
+            let (g, squares) = (&self.g, &self.squares);
+            let n = g.len();
+            let m = n / 2;
+            let next = if n % 2 == 1 {
+                // psi_{2m+1} = psi_{m+2} psi_m^3 - psi_{m-1} psi_{m+1}^3
+                let u = mul(&g[m + 2], &mul(&squares[m], &g[m]));
+                let v = mul(&g[m - 1], &mul(&squares[m + 1], &g[m + 1]));
+                if m % 2 == 0 {
+                    ring.sub(&mul(&self.f2, &u), &v)
+                } else {
+                    ring.sub(&u, &mul(&self.f2, &v))
+                }
+            } else {
+                // psi_2m = psi_m (psi_{m+2} psi_{m-1}^2 - psi_{m-2} psi_{m+1}^2) / 2y
+                let u = mul(&g[m + 2], &squares[m - 1]);
+                let v = mul(&g[m - 2], &squares[m + 1]);
+                ring.scale(&mul(&g[m], &ring.sub(&u, &v)), &self.half)
+            };
+            self.squares.push(mul(&next, &next));
+            self.g.push(next);
+        }
+    }
+}
+
+impl<'a> Schoof<'a> {
+    // division polynomials up to the largest prime used, plus two
+    fn new(ec: &'a EllipticCurve, max_l: u64) -> Self {
+        let p = ec.p();
+        let f = Poly::new(vec![
+            ec.b().clone(),
+            ec.a().clone(),
+            BigUint::zero(),
+            BigUint::one(),
+        ]);
+        let mut schoof = Schoof {
+            p,
+            a: ec.a().clone(),
+            b: ec.b().clone(),
+            f,
+            g: Vec::new(),
+        };
+        let ring = schoof.ring();
+        let mul = |u: &Poly, v: &Poly| ring.mul(u, v);
+        let mut g = DivisionPolynomials::new(&schoof, &Poly::x(), &schoof.f, &mul);
+        g.extend_to(max_l as usize + 2);
+        schoof.g = g.g;
+        schoof
+    }
+
+    fn ring(&self) -> PolyRing<'a> {
+        PolyRing::new(self.p)
+    }
+
+    fn constant(&self, c: impl Into<BigUint>) -> Poly {
+        Poly::constant(c.into() % self.p)
+    }
+
+    // [n](x, y) = (xn / xd, y yn / yd) from division polynomials g at x
+    fn multiple(
+        &self,
+        n: usize,
+        g: &[Poly],
+        x: &Poly,
+        f: &Poly,
+        modulus: &PolyModulus,
+    ) -> (Poly, Poly, Poly, Poly) {
+        let ring = modulus.ring();
+        let mul = |u: &Poly, v: &Poly| modulus.mul(u, v);
+        // psi_{-1} = -psi_1
+        let minus_one = ring.neg(&self.constant(1u32));
+        let g_minus_two = if n == 1 { &minus_one } else { &g[n - 2] };
+        let (g_prev, g_n, g_next) = (&g[n - 1], &g[n], &g[n + 1]);
+        let g_n2 = mul(g_n, g_n);
+        let yn = ring.sub(
+            &mul(&g[n + 2], &mul(g_prev, g_prev)),
+            &mul(g_minus_two, &mul(g_next, g_next)),
+        );
+        let four_g_n3 = ring.scale(&mul(&g_n2, g_n), &BigUint::from(4u32));
+        if n % 2 == 1 {
+            let xn = ring.sub(&mul(x, &g_n2), &mul(f, &mul(g_prev, g_next)));
+            (xn, g_n2, yn, four_g_n3)
+        } else {
+            let xd = mul(f, &g_n2);
+            let xn = ring.sub(&mul(x, &xd), &mul(g_prev, g_next));
+            let yd = mul(&mul(f, f), &four_g_n3);
+            (xn, xd, yn, yd)
+        }
+    }
+
+    // t mod l
+    fn trace_mod(&self, l: u64) -> u64 {
+        let ring = self.ring();
+        let p = self.p;
+        if l == 2 {
+            // t is even exactly when there is a point of order 2, a root of f
+            let modulus = PolyModulus::new(ring, &self.f);
+            let xp = modulus.pow(&Poly::x(), p);
+            let ring = modulus.ring();
+            let h = ring.gcd(&ring.sub(&xp, &Poly::x()), &self.f);
+            return if h.degree() > 0 { 0 } else { 1 };
+        }
+
+        // work on the l-torsion, modulo psi_l
+        let l_usize = l as usize;
+        let modulus = PolyModulus::new(ring, &self.g[l_usize]);
+        let ring = modulus.ring();
+        let mul = |u: &Poly, v: &Poly| modulus.mul(u, v);
+        let is_one = |h: &Poly| h.degree() == 0;
+
+        // Frobenius (x^p, y^p) = (xp, y yp) and its square (xp2, y yp2)
+        let x = modulus.reduce(&Poly::x());
+        let f = modulus.reduce(&self.f);
+        let xp = modulus.pow(&x, p);
+        let yp = modulus.pow(&f, &((p - 1u32) >> 1));
+        // g(x)^p = g(x^p), so the p-th powers of xp and yp are compositions
+        let frobenius = modulus.compose(&[&xp, &yp], &xp);
+        let xp2 = &frobenius[0];
+        let yp2 = mul(&frobenius[1], &yp);
+
+        // [n](x, y) as (xn, y yn) for 0 < n < l, where psi_n is a unit
+        let point = |n: u64| {
+            let n = n as usize;
+            let g: Vec<Poly> = self.g[..n + 3].iter().map(|u| modulus.reduce(u)).collect();
+            let (xn, xd, yn, yd) = self.multiple(n, &g, &x, &f, &modulus);
+            let inv = |u: &Poly| modulus.gcd_inverse(u).1;
+            (mul(&xn, &inv(&xd)), mul(&yn, &inv(&yd)))
+        };
+        let q = (p % l).to_u64().expect("l is small");
+        let (xq, yq) = point(q);
+
+        let (h, dx_inv) = modulus.gcd_inverse(&ring.sub(xp2, &xq));
+        if !is_one(&h) {
+            // pi^2 P = +-q P for some P
+            let w = match (1..l).find(|w| w * w % l == q) {
+                Some(w) => w,
+                None => return 0,
+            };
+            let (xw, yw) = point(w);
+            let h = ring.gcd(&ring.sub(&xp, &xw), modulus.modulus());
+            if is_one(&h) {
+                return 0;
+            }
+            // pi P = w P or pi P = -w P on these points, t = 2w or -2w
+            let dy = ring.rem(&ring.sub(&yp, &yw), &h);
+            return if is_one(&ring.gcd(&dy, &h)) {
+                (l - 2 * w % l) % l
+            } else {
+                2 * w % l
+            };
+        }
+
+        // (x3, y y3) = pi^2 P + q P, with slope y lambda
+        let lambda = mul(&ring.sub(&yp2, &yq), &dx_inv);
+        let x3 = ring.sub(&ring.sub(&mul(&f, &mul(&lambda, &lambda)), xp2), &xq);
+        let y3 = ring.sub(&mul(&lambda, &ring.sub(xp2, &x3)), &yp2);
+
+        // Find tau with pi^2 P + q P = tau pi P. The multiples of pi P come
+        // from G_n = g_n(xp), with F = f(xp) in place of y^2: x(tau pi P) is
+        // xp - F G_{tau-1} G_{tau+1} / G_tau^2 for odd tau and
+        // xp - G_{tau-1} G_{tau+1} / (F G_tau^2) for even tau. Only the
+        // y-coordinate of the match is needed, for the sign.
+        let fp = modulus.reduce(&ring.add(
+            &ring.add(&mul(&mul(&xp, &xp), &xp), &ring.scale(&xp, &self.a)),
+            &self.constant(self.b.clone()),
+        ));
+        let mut gp = DivisionPolynomials::new(self, &xp, &fp, &mul);
+        let dx = ring.sub(&x3, &xp);
+        let dx_f = mul(&dx, &fp);
+        for tau in 1..=l_usize / 2 {
+            gp.extend_to(tau + 2);
+            let (g, squares) = (&gp.g, &gp.squares);
+            let cross = mul(&g[tau - 1], &g[tau + 1]);
+            let x_matches = if tau % 2 == 1 {
+                ring.add(&mul(&dx, &squares[tau]), &mul(&fp, &cross))
+            } else {
+                ring.add(&mul(&dx_f, &squares[tau]), &cross)
+            }
+            .is_zero();
+            if x_matches {
+                let (_, _, yn, yd) = self.multiple(tau, g, &xp, &fp, &modulus);
+                let tau = tau as u64;
+                return if mul(&y3, &yd) == mul(&yp, &yn) {
+                    tau
+                } else {
+                    l - tau
+                };
+            }
+        }
+        unreachable!(
+            "Frobenius satisfies its characteristic polynomial mod {}",
+            l
+        )
+    }
+}
+
+// t mod the product of the primes, from t mod each one
+fn crt(residues: &[(u64, u64)]) -> BigUint {
+    let mut t = BigUint::zero();
+    let mut m = BigUint::one();
+    for &(l, r) in residues {
+        let big_l = BigUint::from(l);
+        let m_inv = (&m % &big_l).modpow(&BigUint::from(l - 2), &big_l);
+        let t_mod = &t % &big_l;
+        let k = (BigUint::from(r) + &big_l - t_mod) * m_inv % &big_l;
+        t += &m * k;
+        m *= l;
+    }
+    t
+}
+
+fn next_prime(n: u64) -> u64 {
+    (n + 1..)
+        .find(|&k| k >= 2 && (2..).take_while(|d| d * d <= k).all(|d| k % d != 0))
+        .expect("there is always a next prime")
+}
+
+fn point_hash(c: &Point) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    c.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod schoof_test {
+    use super::{BigUint, EllipticCurve, Point};
+    use crate::curves;
+
+    #[test]
+    fn test_matches_point_counting() {
+        // includes y^2 = x^3 - x (full 2-torsion) and the supersingular
+        // y^2 = x^3 + 1 for p = 2 mod 3
+        for (a, b, p) in [
+            (2u32, 2u32, 17u32),
+            (2, 3, 17),
+            (22, 0, 23),
+            (0, 1, 101),
+            (3, 7, 1009),
+            (1000, 5, 10007),
+            (0, 7, 10009),
+        ] {
+            let ec = EllipticCurve::new(BigUint::from(a), BigUint::from(b), BigUint::from(p));
+            let expected = ec.count_points();
+            assert_eq!(ec.schoof_count(0), Ok(expected.clone()), "{:?}", (a, b, p));
+            assert_eq!(ec.count_points_schoof(), expected, "{:?}", (a, b, p));
+        }
+    }
+
+    #[test]
+    fn test_medium_curve() {
+        // p = 2^45 + 59: Schoof alone and baby-step giant-step agree, and
+        // the count kills random points
+        let ec = EllipticCurve::new(
+            BigUint::from(3u32),
+            BigUint::from(7u32),
+            BigUint::from((1u64 << 45) + 59),
+        );
+        let order = ec.count_points_schoof();
+        assert_eq!(ec.schoof_count(0), Ok(order.clone()));
+        for _ in 0..4 {
+            assert_eq!(ec.scalar_mul(&ec.random_point(), &order), Point::Identity);
+        }
+    }
+
+    #[test]
+    fn test_known_order() {
+        // y^2 = x^3 - 3x + 17 over p = 2^64 - 189 has prime order n. To get
+        // down to 2^16 candidates for baby-step giant-step, Schoof has to
+        // find t mod l for every prime l up to 17.
+        let p = BigUint::from(u64::MAX - 188);
+        let ec = EllipticCurve::new(&p - 3u32, BigUint::from(17u32), p);
+        let n = BigUint::from(18446744079795905101u128);
+        assert_eq!(ec.schoof_count(16), Ok(n));
+    }
+
+    // Five to ten minutes each in release mode:
+    // cargo test --release -- --ignored schoof
+    #[test]
+    #[ignore]
+    fn test_secp256k1_order() {
+        let params = curves::secp256k1();
+        assert_eq!(&params.curve().count_points_schoof(), params.order());
+    }
+
+    #[test]
+    #[ignore]
+    fn test_p256_order() {
+        let params = curves::by_name("P-256").expect("P-256 is registered");
+        assert_eq!(&params.curve().count_points_schoof(), params.order());
+    }
+}
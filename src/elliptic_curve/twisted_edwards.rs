@@ -0,0 +1,569 @@
+use super::curve_group::{check_ladder_bits, CurveGroup};
+use super::elliptic_curve::Point;
+use super::field_element::FieldElement;
+use super::finite_field::FiniteField;
+use super::ladder::conditional_swap;
+use super::primality::is_prime;
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+
+// Twisted Edwards curve a x^2 + y^2 = 1 + d x^2 y^2 over F_p. The neutral
+// element is the affine point (0, 1); it is written Point::Identity so that
+// generic code sees the usual identity. When a is a square and d is not, as
+// on edwards25519 and edwards448, the addition law has no exceptions at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TwistedEdwardsCurve {
+    a: BigUint,
+    d: BigUint,
+    field: FiniteField,
+}
+
+// Extended coordinates (Hisil-Wong-Carter-Dawson): (X : Y : Z : T) stands
+// for (X / Z, Y / Z) with T = XY / Z, and the neutral element is
+// (0 : 1 : 1 : 0).
+#[derive(Debug, Clone)]
+pub struct ExtendedPoint<'a> {
+    x: FieldElement<'a>,
+    y: FieldElement<'a>,
+    z: FieldElement<'a>,
+    t: FieldElement<'a>,
+}
+
+impl<'a> ExtendedPoint<'a> {
+    pub fn x(&self) -> &FieldElement<'a> {
+        &self.x
+    }
+
+    pub fn y(&self) -> &FieldElement<'a> {
+        &self.y
+    }
+
+    pub fn z(&self) -> &FieldElement<'a> {
+        &self.z
+    }
+
+    pub fn t(&self) -> &FieldElement<'a> {
+        &self.t
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.x.is_zero() && self.y == self.z
+    }
+}
+
+// (X1 : Y1 : Z1 : T1) = (X2 : Y2 : Z2 : T2) iff X1 Z2 = X2 Z1 and Y1 Z2 = Y2 Z1
+impl PartialEq for ExtendedPoint<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        &self.x * &other.z == &other.x * &self.z && &self.y * &other.z == &other.y * &self.z
+    }
+}
+
+impl Eq for ExtendedPoint<'_> {}
+
+impl TwistedEdwardsCurve {
+    pub fn new(a: BigUint, d: BigUint, p: BigUint) -> Self {
+        TwistedEdwardsCurve {
+            a,
+            d,
+            field: FiniteField::new(p),
+        }
+    }
+
+    pub fn a(&self) -> &BigUint {
+        &self.a
+    }
+
+    pub fn d(&self) -> &BigUint {
+        &self.d
+    }
+
+    pub fn p(&self) -> &BigUint {
+        self.field.p()
+    }
+
+    pub fn field(&self) -> &FiniteField {
+        &self.field
+    }
+
+    // p must be an odd prime, a and d non-zero and a != d (mod p)
+    pub fn validate(&self) -> Result<()> {
+        if !is_prime(self.p()) || self.p() == &BigUint::from(2u32) {
+            return Err(EccError::ModulusNotPrime);
+        }
+        let f = &self.field;
+        let (a, d) = (f.element(&self.a), f.element(&self.d));
+        if a.is_zero() || d.is_zero() || a == d {
+            return Err(EccError::SingularCurve);
+        }
+        Ok(())
+    }
+
+    pub fn is_on_curve(&self, c: &Point) -> bool {
+        match c {
+            Point::Coordinates(x, y) => {
+                let f = &self.field;
+                if x >= self.p() || y >= self.p() {
+                    return false;
+                }
+                let (x2, y2) = (f.element(x).square(), f.element(y).square());
+                // a x^2 + y^2 = 1 + d x^2 y^2
+                f.element(&self.a) * &x2 + &y2 == f.one() + f.element(&self.d) * x2 * y2
+            }
+            Point::Identity => true,
+        }
+    }
+
+    pub fn extended_identity(&self) -> ExtendedPoint<'_> {
+        let f = &self.field;
+        ExtendedPoint {
+            x: f.zero(),
+            y: f.one(),
+            z: f.one(),
+            t: f.zero(),
+        }
+    }
+
+    // (x, y) -> (x : y : 1 : xy); does not check that c is on the curve
+    pub fn to_extended(&self, c: &Point) -> ExtendedPoint<'_> {
+        let f = &self.field;
+        match c {
+            Point::Coordinates(x, y) => {
+                let (x, y) = (f.element(x), f.element(y));
+                ExtendedPoint {
+                    t: &x * &y,
+                    x,
+                    y,
+                    z: f.one(),
+                }
+            }
+            Point::Identity => self.extended_identity(),
+        }
+    }
+
+    pub fn extended_to_affine(&self, c: &ExtendedPoint) -> Point {
+        self.try_extended_to_affine(c)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // fails only for Z = 0, which the complete formulas never produce
+    pub fn try_extended_to_affine(&self, c: &ExtendedPoint) -> Result<Point> {
+        let z_inv = c.z.try_inv()?;
+        let (x, y) = (&c.x * &z_inv, &c.y * &z_inv);
+        if x.is_zero() && y == self.field.one() {
+            return Ok(Point::Identity);
+        }
+        Ok(Point::Coordinates(x.value(), y.value()))
+    }
+
+    // "add-2008-hwcd", the unified addition in extended coordinates:
+    // 9M + 1 m_a + 1 m_d. r == q and the neutral element need no special
+    // case, and for a square, d non-square the formula is complete.
+    pub fn extended_add<'a>(
+        &'a self,
+        r: &ExtendedPoint<'a>,
+        q: &ExtendedPoint<'a>,
+    ) -> ExtendedPoint<'a> {
+        let f = &self.field;
+        let a = f.element(&self.a);
+        let d = f.element(&self.d);
+
+        let aa = &r.x * &q.x;
+        let bb = &r.y * &q.y;
+        let cc = d * &r.t * &q.t;
+        let dd = &r.z * &q.z;
+        // E = X1 Y2 + X2 Y1
+        let e = (&r.x + &r.y) * (&q.x + &q.y) - &aa - &bb;
+        let ff = &dd - &cc;
+        let g = &dd + &cc;
+        let h = bb - a * aa;
+        ExtendedPoint {
+            x: &e * &ff,
+            y: &g * &h,
+            z: ff * g,
+            t: e * h,
+        }
+    }
+
+    // "dbl-2008-hwcd": 4M + 4S + 1 m_a, without T on the input
+    pub fn extended_double<'a>(&'a self, c: &ExtendedPoint<'a>) -> ExtendedPoint<'a> {
+        let a = self.field.element(&self.a);
+
+        let aa = c.x.square();
+        let bb = c.y.square();
+        let cc = c.z.square() + c.z.square();
+        let dd = a * &aa;
+        // E = 2 X1 Y1
+        let e = (&c.x + &c.y).square() - &aa - &bb;
+        let g = &dd + &bb;
+        let ff = &g - cc;
+        let h = dd - bb;
+        ExtendedPoint {
+            x: &e * &ff,
+            y: &g * &h,
+            z: ff * g,
+            t: e * h,
+        }
+    }
+
+    pub fn add(&self, r: &Point, q: &Point) -> Point {
+        self.try_add(r, q).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_add(&self, r: &Point, q: &Point) -> Result<Point> {
+        self.check_on_curve(r)?;
+        self.check_on_curve(q)?;
+        let sum = self.extended_add(&self.to_extended(r), &self.to_extended(q));
+        self.try_extended_to_affine(&sum)
+    }
+
+    pub fn double(&self, c: &Point) -> Point {
+        self.try_double(c).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_double(&self, c: &Point) -> Result<Point> {
+        self.check_on_curve(c)?;
+        self.try_extended_to_affine(&self.extended_double(&self.to_extended(c)))
+    }
+
+    // -(x, y) = (-x, y)
+    pub fn negate(&self, c: &Point) -> Point {
+        match c {
+            Point::Coordinates(x, y) => {
+                Point::Coordinates((-self.field.element(x)).value(), y.clone())
+            }
+            Point::Identity => Point::Identity,
+        }
+    }
+
+    pub fn scalar_mul(&self, c: &Point, d: &BigUint) -> Point {
+        self.try_scalar_mul(c, d)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_scalar_mul(&self, c: &Point, d: &BigUint) -> Result<Point> {
+        CurveGroup::try_scalar_mul(self, c, d)
+    }
+
+    // RFC 8032 encoding (5.1.2, 5.2.2): y in little-endian with the low bit
+    // of x in the top bit of the last byte, floor(bits(p) / 8) + 1 bytes in
+    // all, i.e. 32 for edwards25519 and 57 for edwards448
+    pub fn encode_point(&self, c: &Point) -> Vec<u8> {
+        let (x, y) = match c {
+            Point::Coordinates(x, y) => (x.clone(), y.clone()),
+            Point::Identity => (BigUint::from(0u32), BigUint::from(1u32)),
+        };
+        let mut bytes = y.to_bytes_le();
+        bytes.resize(self.encoded_len(), 0);
+        if x.bit(0) {
+            *bytes.last_mut().expect("the encoding is not empty") |= 0x80;
+        }
+        bytes
+    }
+
+    pub fn decode_point(&self, bytes: &[u8]) -> Point {
+        self.try_decode_point(bytes)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // RFC 8032 decoding (5.1.3, 5.2.3): x^2 = (y^2 - 1) / (d y^2 - a), and
+    // the sign bit picks the root. Non-canonical y >= p and the encoding of
+    // x = 0 with the sign bit set are rejected.
+    pub fn try_decode_point(&self, bytes: &[u8]) -> Result<Point> {
+        let len = self.encoded_len();
+        if bytes.len() != len {
+            return Err(EccError::InvalidEncoding(format!(
+                "expected {} bytes, got {}",
+                len,
+                bytes.len()
+            )));
+        }
+        let sign = bytes[len - 1] >> 7 == 1;
+        let mut bytes = bytes.to_vec();
+        bytes[len - 1] &= 0x7f;
+        let y = BigUint::from_bytes_le(&bytes);
+        if &y >= self.p() {
+            return Err(EccError::InvalidEncoding("y is not below p".to_string()));
+        }
+
+        let f = &self.field;
+        let y2 = f.element(&y).square();
+        let x2 = (&y2 - f.one())
+            .try_div(&(f.element(&self.d) * &y2 - f.element(&self.a)))
+            .map_err(|_| EccError::InvalidEncoding("no point with this y".to_string()))?;
+        let x = if x2.is_zero() {
+            if sign {
+                return Err(EccError::InvalidEncoding(
+                    "x = 0 with the sign bit set".to_string(),
+                ));
+            }
+            BigUint::from(0u32)
+        } else {
+            let (root, other) = f
+                .sqrt(&x2.value())
+                .ok_or_else(|| EccError::InvalidEncoding("no point with this y".to_string()))?;
+            if root.bit(0) == sign {
+                root
+            } else {
+                other
+            }
+        };
+        if x == BigUint::from(0u32) && y == BigUint::from(1u32) {
+            return Ok(Point::Identity);
+        }
+        Ok(Point::Coordinates(x, y))
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.p().bits() as usize / 8 + 1
+    }
+
+    fn check_on_curve(&self, c: &Point) -> Result<()> {
+        if !self.is_on_curve(c) {
+            return Err(EccError::NotOnCurve(c.clone()));
+        }
+        Ok(())
+    }
+}
+
+fn swap_points<'a>(bit: &FieldElement<'a>, r: &mut ExtendedPoint<'a>, q: &mut ExtendedPoint<'a>) {
+    conditional_swap(bit, &mut r.x, &mut q.x);
+    conditional_swap(bit, &mut r.y, &mut q.y);
+    conditional_swap(bit, &mut r.z, &mut q.z);
+    conditional_swap(bit, &mut r.t, &mut q.t);
+}
+
+impl CurveGroup for TwistedEdwardsCurve {
+    fn is_on_curve(&self, c: &Point) -> bool {
+        TwistedEdwardsCurve::is_on_curve(self, c)
+    }
+
+    fn try_add(&self, r: &Point, q: &Point) -> Result<Point> {
+        TwistedEdwardsCurve::try_add(self, r, q)
+    }
+
+    fn try_double(&self, c: &Point) -> Result<Point> {
+        TwistedEdwardsCurve::try_double(self, c)
+    }
+
+    fn negate(&self, c: &Point) -> Point {
+        TwistedEdwardsCurve::negate(self, c)
+    }
+
+    fn field_order(&self) -> BigUint {
+        self.p().clone()
+    }
+
+    fn validate(&self) -> Result<()> {
+        TwistedEdwardsCurve::validate(self)
+    }
+
+    // double-and-add in extended coordinates, one inversion at the end
+    fn try_scalar_mul(&self, c: &Point, d: &BigUint) -> Result<Point> {
+        self.check_on_curve(c)?;
+        let q = self.to_extended(c);
+        let mut acc = self.extended_identity();
+        for i in (0..d.bits()).rev() {
+            acc = self.extended_double(&acc);
+            if d.bit(i) {
+                acc = self.extended_add(&acc, &q);
+            }
+        }
+        self.try_extended_to_affine(&acc)
+    }
+
+    // The ladder on the complete formulas: a conditional swap, one unified
+    // addition and one doubling per bit, with no branch on the bits of d.
+    fn try_ladder_scalar_mul(&self, c: &Point, d: &BigUint, bits: u64) -> Result<Point> {
+        self.check_on_curve(c)?;
+        check_ladder_bits(d, bits)?;
+
+        let f = &self.field;
+        let mut r0 = self.extended_identity();
+        let mut r1 = self.to_extended(c);
+        for i in (0..bits).rev() {
+            let bit = f.element(&BigUint::from(d.bit(i) as u8));
+            swap_points(&bit, &mut r0, &mut r1);
+            r1 = self.extended_add(&r0, &r1);
+            r0 = self.extended_double(&r0);
+            swap_points(&bit, &mut r0, &mut r1);
+        }
+        self.try_extended_to_affine(&r0)
+    }
+}
+
+#[cfg(test)]
+mod twisted_edwards_test {
+    use super::{BigUint, CurveGroup, EccError, Point, TwistedEdwardsCurve};
+    use crate::curves;
+    use num_bigint::RandBigInt;
+
+    fn scalar(hex: &str) -> BigUint {
+        BigUint::parse_bytes(hex.as_bytes(), 16).expect("could not convert str to scalar")
+    }
+
+    #[test]
+    fn test_small_curve() {
+        // x^2 + y^2 = 1 + 5 x^2 y^2 over F_13: 5 is a non-square, so the
+        // addition law is complete; the group has 16 points
+        let ec = TwistedEdwardsCurve::new(
+            BigUint::from(1u32),
+            BigUint::from(5u32),
+            BigUint::from(13u32),
+        );
+        assert_eq!(ec.validate(), Ok(()));
+        let mut points = vec![Point::Identity];
+        for x in 0..13u32 {
+            for y in 0..13u32 {
+                let c = Point::Coordinates(BigUint::from(x), BigUint::from(y));
+                if (x, y) != (0, 1) && ec.is_on_curve(&c) {
+                    points.push(c);
+                }
+            }
+        }
+        assert_eq!(points.len(), 16);
+
+        for r in &points {
+            assert_eq!(ec.add(r, &ec.negate(r)), Point::Identity);
+            assert_eq!(ec.double(r), ec.add(r, r));
+            assert_eq!(ec.scalar_mul(r, &BigUint::from(16u32)), Point::Identity);
+            for q in &points {
+                let sum = ec.add(r, q);
+                assert!(ec.is_on_curve(&sum));
+                assert_eq!(sum, ec.add(q, r));
+            }
+        }
+        // the ladder agrees with double-and-add
+        let c = &points[5];
+        for k in 0..24u32 {
+            let k = BigUint::from(k);
+            assert_eq!(ec.try_ladder_scalar_mul(c, &k, 5), Ok(ec.scalar_mul(c, &k)));
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let p = BigUint::from(13u32);
+        let curve = |a: u32, d: u32, p: &BigUint| {
+            TwistedEdwardsCurve::new(BigUint::from(a), BigUint::from(d), p.clone()).validate()
+        };
+        assert_eq!(curve(1, 0, &p), Err(EccError::SingularCurve));
+        assert_eq!(curve(0, 3, &p), Err(EccError::SingularCurve));
+        assert_eq!(curve(3, 3, &p), Err(EccError::SingularCurve));
+        assert_eq!(
+            curve(1, 3, &BigUint::from(15u32)),
+            Err(EccError::ModulusNotPrime)
+        );
+    }
+
+    #[test]
+    fn test_rfc8032_public_keys() {
+        // public key = encode(s B), s the clamped hash of the RFC's secret
+        // key: SHA-512 for Ed25519 (7.1, test 1), SHAKE256 for Ed448 (7.4)
+        let params = curves::edwards25519();
+        let (ec, g) = (params.curve(), params.generator());
+        assert_eq!(
+            hex::encode(ec.encode_point(g)),
+            "5866666666666666666666666666666666666666666666666666666666666666"
+        );
+        let s = scalar("4fe94d9006f020a5a3c080d96827fffd3c010ac0f12e7a42cb33284f86837c30");
+        let public_key = ec.scalar_mul(g, &s);
+        assert_eq!(
+            hex::encode(ec.encode_point(&public_key)),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+        assert_eq!(ec.try_ladder_scalar_mul(g, &s, 255), Ok(public_key));
+
+        let params = curves::edwards448();
+        let (ec, g) = (params.curve(), params.generator());
+        let s = scalar("b7bbc01fa70105a74feece1566f5f98374d1ee1ed836c005b99c51381d5e0275eef3a45b54f011b488a572f46766edc78e80a0cea03039e8");
+        assert_eq!(
+            hex::encode(ec.encode_point(&ec.scalar_mul(g, &s))),
+            "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d80e96778edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180"
+        );
+    }
+
+    #[test]
+    fn test_encoding() {
+        let mut rng = rand::thread_rng();
+        for params in [curves::edwards25519(), curves::edwards448()] {
+            let (ec, g) = (params.curve(), params.generator());
+            for c in [
+                Point::Identity,
+                g.clone(),
+                ec.negate(g),
+                ec.scalar_mul(g, &rng.gen_biguint_below(params.order())),
+            ] {
+                let bytes = ec.encode_point(&c);
+                assert_eq!(ec.decode_point(&bytes), c);
+            }
+            // a point of order 2 when a = -1, of order 4 when a = 1
+            let bottom = Point::Coordinates(BigUint::from(0u32), ec.p() - 1u32);
+            assert_eq!(ec.decode_point(&ec.encode_point(&bottom)), bottom);
+        }
+
+        let ec = curves::edwards25519().curve();
+        assert!(ec.try_decode_point(&[0; 31]).is_err());
+        // y = p, and x = 0 with the sign bit set
+        let mut bytes = ec.p().to_bytes_le();
+        assert!(ec.try_decode_point(&bytes).is_err());
+        bytes = BigUint::from(1u32).to_bytes_le();
+        bytes.resize(32, 0);
+        bytes[31] |= 0x80;
+        assert!(ec.try_decode_point(&bytes).is_err());
+        // y = 2 is not the y-coordinate of any point on edwards25519
+        let mut bytes = vec![0; 32];
+        bytes[0] = 2;
+        assert!(ec.try_decode_point(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_generic_strategies() {
+        // wNAF, multi-scalar and fixed-base multiplication come from
+        // CurveGroup and run on the affine group law
+        let params = curves::edwards25519();
+        let (ec, g) = (params.curve(), params.generator());
+        let mut rng = rand::thread_rng();
+        let k1 = rng.gen_biguint_below(params.order());
+        let k2 = rng.gen_biguint_below(params.order());
+        let (p1, p2) = (ec.scalar_mul(g, &k1), ec.scalar_mul(g, &k2));
+        assert_eq!(ec.try_scalar_mul_vartime(g, &k1, 4), Ok(p1.clone()));
+        let table = ec.fixed_base_table(g, 253, 4).expect("G is on the curve");
+        // the fallback table keeps no rows and runs the ladder over 256 bits
+        assert!(table.rows().is_empty());
+        assert_eq!(table.max_bits(), 256);
+        assert_eq!(ec.try_fixed_base_mul(&table, &k2), Ok(p2.clone()));
+        assert_eq!(
+            ec.try_multi_scalar_mul(&[(g.clone(), k2.clone()), (p1.clone(), k1.clone())], 4),
+            Ok(ec.add(&p2, &ec.scalar_mul(&p1, &k1)))
+        );
+
+        // (0, -1) has order 2 and lies outside the subgroup of order L
+        let bottom = Point::Coordinates(BigUint::from(0u32), ec.p() - 1u32);
+        assert!(params.is_in_subgroup(&p1));
+        assert!(!params.is_in_subgroup(&bottom));
+        assert_eq!(params.point_order(&bottom), BigUint::from(2u32));
+        assert_eq!(params.clear_cofactor(&bottom), Point::Identity);
+    }
+
+    #[test]
+    fn test_matches_x25519() {
+        // edwards25519 and Curve25519 are birationally equivalent with
+        // u = (1 + y) / (1 - y), and the base points correspond
+        let params = curves::edwards25519();
+        let (ec, g) = (params.curve(), params.generator());
+        let montgomery = curves::curve25519();
+        let f = ec.field();
+        let mut rng = rand::thread_rng();
+        for _ in 0..4 {
+            let k = rng.gen_biguint(255);
+            let u = match ec.scalar_mul(g, &k) {
+                Point::Coordinates(_, y) => {
+                    let y = f.element(&y);
+                    ((f.one() + &y) / (f.one() - &y)).value()
+                }
+                Point::Identity => BigUint::from(0u32),
+            };
+            assert_eq!(montgomery.ladder(&k, &BigUint::from(9u32), 255), u);
+        }
+    }
+}
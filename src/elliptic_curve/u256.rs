@@ -0,0 +1,345 @@
+use num_bigint::BigUint;
+use std::cmp::Ordering;
+
+// 256-bit unsigned integer as four little-endian 64-bit limbs, kept on the stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct U256(pub [u64; 4]);
+
+impl U256 {
+    pub const ZERO: U256 = U256([0, 0, 0, 0]);
+    pub const ONE: U256 = U256([1, 0, 0, 0]);
+
+    // Returns None if the value does not fit in 256 bits.
+    pub fn from_biguint(n: &BigUint) -> Option<Self> {
+        let digits = n.to_u64_digits();
+        if digits.len() > 4 {
+            return None;
+        }
+        let mut limbs = [0u64; 4];
+        limbs[..digits.len()].copy_from_slice(&digits);
+        Some(U256(limbs))
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        let bytes: Vec<u8> = self.0.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+        BigUint::from_bytes_le(&bytes)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0, 0, 0, 0]
+    }
+
+    pub fn bit(&self, i: usize) -> bool {
+        (self.0[i / 64] >> (i % 64)) & 1 == 1
+    }
+
+    // a + b, returning the carry out of the top limb
+    pub fn overflowing_add(&self, rhs: &U256) -> (U256, bool) {
+        let mut limbs = [0u64; 4];
+        let mut carry = 0u64;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let sum = self.0[i] as u128 + rhs.0[i] as u128 + carry as u128;
+            *limb = sum as u64;
+            carry = (sum >> 64) as u64;
+        }
+        (U256(limbs), carry != 0)
+    }
+
+    // a - b, returning the borrow out of the top limb
+    pub fn overflowing_sub(&self, rhs: &U256) -> (U256, bool) {
+        let mut limbs = [0u64; 4];
+        let mut borrow = 0u64;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let (d, b1) = self.0[i].overflowing_sub(rhs.0[i]);
+            let (d, b2) = d.overflowing_sub(borrow);
+            *limb = d;
+            borrow = (b1 || b2) as u64;
+        }
+        (U256(limbs), borrow != 0)
+    }
+
+    // full 512-bit product, little-endian limbs
+    pub fn mul_wide(&self, rhs: &U256) -> [u64; 8] {
+        let mut wide = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let t = wide[i + j] as u128 + self.0[i] as u128 * rhs.0[j] as u128 + carry;
+                wide[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            wide[i + 4] = carry as u64;
+        }
+        wide
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+// Primes with a dedicated reduction routine for 512-bit products.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecialPrime {
+    // p = 2^256 - 2^32 - 977
+    Secp256k1,
+    // p = 2^256 - 2^224 + 2^192 + 2^96 - 1
+    P256,
+}
+
+const SECP256K1_P: U256 = U256([
+    0xFFFFFFFEFFFFFC2F,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+]);
+
+// 2^256 mod p for secp256k1
+const SECP256K1_C: u64 = 0x1000003D1;
+
+const P256_P: U256 = U256([
+    0xFFFFFFFFFFFFFFFF,
+    0x00000000FFFFFFFF,
+    0x0000000000000000,
+    0xFFFFFFFF00000001,
+]);
+
+impl SpecialPrime {
+    pub fn detect(p: &BigUint) -> Option<Self> {
+        match U256::from_biguint(p)? {
+            SECP256K1_P => Some(SpecialPrime::Secp256k1),
+            P256_P => Some(SpecialPrime::P256),
+            _ => None,
+        }
+    }
+
+    pub fn modulus(&self) -> U256 {
+        match self {
+            SpecialPrime::Secp256k1 => SECP256K1_P,
+            SpecialPrime::P256 => P256_P,
+        }
+    }
+
+    // Reduces a value < 2^256 into [0, p).
+    pub fn reduce_once(&self, a: &U256) -> U256 {
+        let (d, borrow) = a.overflowing_sub(&self.modulus());
+        if borrow {
+            *a
+        } else {
+            d
+        }
+    }
+
+    pub fn add(&self, a: &U256, b: &U256) -> U256 {
+        let (sum, carry) = a.overflowing_add(b);
+        let (d, borrow) = sum.overflowing_sub(&self.modulus());
+        // keep sum only if it neither overflowed nor reached p
+        if carry || !borrow {
+            d
+        } else {
+            sum
+        }
+    }
+
+    pub fn sub(&self, a: &U256, b: &U256) -> U256 {
+        let (d, borrow) = a.overflowing_sub(b);
+        if borrow {
+            d.overflowing_add(&self.modulus()).0
+        } else {
+            d
+        }
+    }
+
+    pub fn neg(&self, a: &U256) -> U256 {
+        self.sub(&U256::ZERO, a)
+    }
+
+    pub fn mul(&self, a: &U256, b: &U256) -> U256 {
+        self.reduce_wide(&a.mul_wide(b))
+    }
+
+    pub fn pow(&self, base: &U256, exponent: &BigUint) -> U256 {
+        let mut result = U256::ONE;
+        for i in (0..exponent.bits()).rev() {
+            result = self.mul(&result, &result);
+            if exponent.bit(i) {
+                result = self.mul(&result, base);
+            }
+        }
+        result
+    }
+
+    pub fn reduce_wide(&self, wide: &[u64; 8]) -> U256 {
+        match self {
+            SpecialPrime::Secp256k1 => reduce_secp256k1(wide),
+            SpecialPrime::P256 => reduce_p256(wide),
+        }
+    }
+}
+
+// hi * 2^256 + lo = hi * (2^32 + 977) + lo (mod p), applied twice
+fn reduce_secp256k1(wide: &[u64; 8]) -> U256 {
+    let mut acc = [0u64; 5];
+    let mut carry = 0u128;
+    for i in 0..4 {
+        let t = wide[i] as u128 + wide[i + 4] as u128 * SECP256K1_C as u128 + carry;
+        acc[i] = t as u64;
+        carry = t >> 64;
+    }
+    acc[4] = carry as u64;
+
+    // acc[4] < 2^34, fold it in once more
+    let mut limbs = [0u64; 4];
+    let mut carry = acc[4] as u128 * SECP256K1_C as u128;
+    for i in 0..4 {
+        let t = acc[i] as u128 + carry;
+        limbs[i] = t as u64;
+        carry = t >> 64;
+    }
+
+    let mut result = U256(limbs);
+    if carry != 0 {
+        // wrapped past 2^256 once more, which is worth another 2^256 mod p
+        result = result.overflowing_add(&U256([SECP256K1_C, 0, 0, 0])).0;
+    }
+    SpecialPrime::Secp256k1.reduce_once(&result)
+}
+
+// FIPS 186-4 D.2.3 fast reduction on 32-bit words c0..c15:
+// s1 + 2 s2 + 2 s3 + s4 + s5 - d1 - d2 - d3 - d4 (mod p)
+fn reduce_p256(wide: &[u64; 8]) -> U256 {
+    let mut c = [0i64; 16];
+    for (i, limb) in wide.iter().enumerate() {
+        c[2 * i] = (limb & 0xFFFFFFFF) as i64;
+        c[2 * i + 1] = (limb >> 32) as i64;
+    }
+
+    // each term lists words a0..a7, least significant first
+    let s1 = [c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]];
+    let s2 = [0, 0, 0, c[11], c[12], c[13], c[14], c[15]];
+    let s3 = [0, 0, 0, c[12], c[13], c[14], c[15], 0];
+    let s4 = [c[8], c[9], c[10], 0, 0, 0, c[14], c[15]];
+    let s5 = [c[9], c[10], c[11], c[13], c[14], c[15], c[13], c[8]];
+    let d1 = [c[11], c[12], c[13], 0, 0, 0, c[8], c[10]];
+    let d2 = [c[12], c[13], c[14], c[15], 0, 0, c[9], c[11]];
+    let d3 = [c[13], c[14], c[15], c[8], c[9], c[10], 0, c[12]];
+    let d4 = [c[14], c[15], 0, c[9], c[10], c[11], 0, c[13]];
+
+    let mut words = [0u32; 8];
+    let mut carry = 0i64;
+    for i in 0..8 {
+        let t =
+            s1[i] + 2 * s2[i] + 2 * s3[i] + s4[i] + s5[i] - d1[i] - d2[i] - d3[i] - d4[i] + carry;
+        words[i] = t as u32;
+        carry = t >> 32;
+    }
+
+    let mut limbs = [0u64; 4];
+    for i in 0..4 {
+        limbs[i] = words[2 * i] as u64 | ((words[2 * i + 1] as u64) << 32);
+    }
+    let mut result = U256(limbs);
+
+    // the true value is result + carry * 2^256 with a small signed carry
+    while carry < 0 {
+        let (sum, overflow) = result.overflowing_add(&P256_P);
+        result = sum;
+        carry += overflow as i64;
+    }
+    while carry > 0 {
+        let (d, borrow) = result.overflowing_sub(&P256_P);
+        result = d;
+        carry -= borrow as i64;
+    }
+    SpecialPrime::P256.reduce_once(&result)
+}
+
+#[cfg(test)]
+mod u256_test {
+    use super::{BigUint, SpecialPrime, U256};
+    use num_bigint::RandBigInt;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_biguint_round_trip() {
+        let n = BigUint::parse_bytes(
+            b"79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            16,
+        )
+        .expect("could not convert str to n");
+        let u = U256::from_biguint(&n).expect("n fits in 256 bits");
+        assert_eq!(u.to_biguint(), n);
+        assert_eq!(U256::from_biguint(&(BigUint::from(1u32) << 256)), None);
+    }
+
+    #[test]
+    fn test_detect() {
+        let secp256k1_p = BigUint::parse_bytes(
+            b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .expect("could not convert str to p");
+        let p256_p = BigUint::parse_bytes(
+            b"FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF",
+            16,
+        )
+        .expect("could not convert str to p");
+        assert_eq!(
+            SpecialPrime::detect(&secp256k1_p),
+            Some(SpecialPrime::Secp256k1)
+        );
+        assert_eq!(SpecialPrime::detect(&p256_p), Some(SpecialPrime::P256));
+        assert_eq!(SpecialPrime::detect(&BigUint::from(17u32)), None);
+    }
+
+    fn check_against_biguint(prime: SpecialPrime) {
+        let p = prime.modulus().to_biguint();
+        let mut rng = thread_rng();
+        let mut samples: Vec<BigUint> = (0..200).map(|_| rng.gen_biguint_below(&p)).collect();
+        // edge cases around 0 and p - 1
+        samples.push(BigUint::from(0u32));
+        samples.push(BigUint::from(1u32));
+        samples.push(&p - BigUint::from(1u32));
+        samples.push(&p - BigUint::from(2u32));
+
+        for pair in samples.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            let (ua, ub) = (
+                U256::from_biguint(a).expect("a < p"),
+                U256::from_biguint(b).expect("b < p"),
+            );
+            assert_eq!(prime.mul(&ua, &ub).to_biguint(), (a * b) % &p);
+            assert_eq!(prime.mul(&ua, &ua).to_biguint(), (a * a) % &p);
+            assert_eq!(prime.add(&ua, &ub).to_biguint(), (a + b) % &p);
+            assert_eq!(prime.sub(&ua, &ub).to_biguint(), (a + &p - b) % &p);
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_arithmetic() {
+        check_against_biguint(SpecialPrime::Secp256k1);
+    }
+
+    #[test]
+    fn test_p256_arithmetic() {
+        check_against_biguint(SpecialPrime::P256);
+    }
+
+    #[test]
+    fn test_pow() {
+        let prime = SpecialPrime::P256;
+        let p = prime.modulus().to_biguint();
+        let a = BigUint::from(123456789u32);
+        let e = &p - BigUint::from(2u32);
+        let result = prime.pow(&U256::from_biguint(&a).expect("a < p"), &e);
+        assert_eq!(result.to_biguint(), a.modpow(&e, &p));
+    }
+}
@@ -0,0 +1,226 @@
+use super::elliptic_curve::{EllipticCurve, Point};
+use super::jacobian::JacobianPoint;
+use crate::error::{EccError, Result};
+use num_bigint::BigUint;
+
+// window width used when the caller has no preference
+pub const DEFAULT_WNAF_WIDTH: usize = 5;
+
+// Width-w non-adjacent form of d, least significant digit first. Every
+// non-zero digit is odd with |digit| < 2^(w-1), and any w consecutive
+// digits contain at most one non-zero, so about bits / (w + 1) additions
+// are left instead of bits / 2.
+pub fn wnaf(d: &BigUint, w: usize) -> Vec<i64> {
+    check_wnaf_width(w).unwrap_or_else(|e| panic!("{}", e));
+    let modulus = 1i64 << w;
+    let half = modulus >> 1;
+
+    let mut k = d.clone();
+    let mut digits = Vec::with_capacity(d.bits() as usize + 1);
+    while k != BigUint::from(0u32) {
+        let digit = if k.bit(0) {
+            let low = (k.iter_u64_digits().next().unwrap_or(0) & (modulus as u64 - 1)) as i64;
+            let digit = if low >= half { low - modulus } else { low };
+            if digit > 0 {
+                k -= digit as u64;
+            } else {
+                k += (-digit) as u64;
+            }
+            digit
+        } else {
+            0
+        };
+        digits.push(digit);
+        k >>= 1;
+    }
+    digits
+}
+
+// the table of odd multiples needs w >= 2, and digits must fit an i64 shift
+pub(crate) fn check_wnaf_width(w: usize) -> Result<()> {
+    if !(2..=16).contains(&w) {
+        return Err(EccError::InvalidWindow {
+            width: w,
+            min: 2,
+            max: 16,
+        });
+    }
+    Ok(())
+}
+
+// Odd multiples c, 3c, ..., (2^(w-1) - 1)c and their negatives, indexed by
+// (|digit| - 1) / 2.
+pub(crate) struct OddMultiples {
+    positive: Vec<Point>,
+    negative: Vec<Point>,
+}
+
+impl OddMultiples {
+    pub(crate) fn get(&self, digit: i64) -> &Point {
+        let index = (digit.unsigned_abs() as usize - 1) / 2;
+        if digit > 0 {
+            &self.positive[index]
+        } else {
+            &self.negative[index]
+        }
+    }
+
+    // the table of f(c) for a homomorphism f, e.g. the GLV endomorphism
+    pub(crate) fn map(&self, f: impl Fn(&Point) -> Point) -> OddMultiples {
+        OddMultiples {
+            positive: self.positive.iter().map(&f).collect(),
+            negative: self.negative.iter().map(&f).collect(),
+        }
+    }
+}
+
+impl EllipticCurve {
+    pub fn scalar_mul_vartime(&self, c: &Point, d: &BigUint, w: usize) -> Point {
+        self.try_scalar_mul_vartime(c, d, w)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // wNAF scalar multiplication for public scalars only: the sequence of
+    // additions reveals the digits of d. Runs in Jacobian coordinates with
+    // mixed additions against an affine table of odd multiples.
+    pub fn try_scalar_mul_vartime(&self, c: &Point, d: &BigUint, w: usize) -> Result<Point> {
+        check_wnaf_width(w)?;
+        if !self.is_on_curve(c) {
+            return Err(EccError::NotOnCurve(c.clone()));
+        }
+        if self.endomorphism().is_some() {
+            return self.try_multi_scalar_mul_windowed(&[(c.clone(), d.clone())], w);
+        }
+
+        let table = self.odd_multiples(c, w)?;
+        let mut t = self.jacobian_identity();
+        for &digit in wnaf(d, w).iter().rev() {
+            t = self.jacobian_double(&t);
+            if digit != 0 {
+                t = self.jacobian_add_mixed(&t, table.get(digit));
+            }
+        }
+        self.try_to_affine(&t)
+    }
+
+    pub(crate) fn odd_multiples(&self, c: &Point, w: usize) -> Result<OddMultiples> {
+        check_wnaf_width(w)?;
+        let size = 1usize << (w - 2);
+        let c_jacobian = self.to_jacobian(c);
+        let double = self.jacobian_double(&c_jacobian);
+
+        let mut jacobian: Vec<JacobianPoint> = Vec::with_capacity(size);
+        jacobian.push(c_jacobian);
+        for i in 1..size {
+            let next = self.jacobian_add(&jacobian[i - 1], &double);
+            jacobian.push(next);
+        }
+
+        let positive = self.batch_to_affine(&jacobian)?;
+        let negative = positive.iter().map(|p| self.negate(p)).collect();
+        Ok(OddMultiples { positive, negative })
+    }
+}
+
+#[cfg(test)]
+mod wnaf_test {
+    use super::{wnaf, BigUint, EccError, EllipticCurve, Point};
+    use crate::curves;
+    use crate::elliptic_curve::CurveGroup;
+
+    #[test]
+    fn test_wnaf_digits() {
+        let d = BigUint::parse_bytes(
+            b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364140",
+            16,
+        )
+        .expect("could not convert str to d");
+
+        for w in 2..=8 {
+            let digits = wnaf(&d, w);
+            // sum digit_i 2^i == d
+            let (mut positive, mut negative) = (BigUint::from(0u32), BigUint::from(0u32));
+            for (i, &digit) in digits.iter().enumerate() {
+                if digit > 0 {
+                    positive += BigUint::from(digit as u64) << i;
+                } else if digit < 0 {
+                    negative += BigUint::from((-digit) as u64) << i;
+                }
+            }
+            assert_eq!(positive - negative, d);
+
+            for (i, &digit) in digits.iter().enumerate() {
+                if digit != 0 {
+                    assert_eq!(digit.abs() % 2, 1);
+                    assert!(digit.abs() < 1 << (w - 1));
+                    let next = &digits[i + 1..digits.len().min(i + w)];
+                    assert!(next.iter().all(|&x| x == 0));
+                }
+            }
+        }
+        assert!(wnaf(&BigUint::from(0u32), 4).is_empty());
+        // 7 = 8 - 1
+        assert_eq!(wnaf(&BigUint::from(7u32), 2), vec![-1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_matches_scalar_mul() {
+        let ec = EllipticCurve::new(
+            BigUint::from(2u32),
+            BigUint::from(2u32),
+            BigUint::from(17u32),
+        );
+        let g = Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32));
+        // large windows put multiples of the order 19 into the table
+        for w in 2..=7 {
+            for k in 0..60u32 {
+                let k = BigUint::from(k);
+                assert_eq!(ec.scalar_mul_vartime(&g, &k, w), ec.scalar_mul(&g, &k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let params = curves::secp256k1();
+        let (ec, g) = (params.curve(), params.generator());
+        // the registry curve has GLV, which would route both sides through
+        // the same endomorphism code; the plain curve runs the wNAF loop
+        let plain = EllipticCurve::new(ec.a().clone(), ec.b().clone(), ec.p().clone());
+        assert!(plain.endomorphism().is_none());
+        let k = BigUint::parse_bytes(
+            b"AA5E28D6A97A2479A65527F7290311A3624D4CC0FA1578598EE3C2613BF99522",
+            16,
+        )
+        .expect("could not convert str to k");
+        let expected = plain.ladder_scalar_mul(g, &k, 256);
+        assert_eq!(plain.scalar_mul(g, &k), expected);
+        for w in 2..=8 {
+            assert_eq!(plain.scalar_mul_vartime(g, &k, w), expected);
+            assert_eq!(ec.scalar_mul_vartime(g, &k, w), expected);
+        }
+    }
+
+    #[test]
+    fn test_invalid_width() {
+        let params = curves::secp256k1();
+        let (ec, g) = (params.curve(), params.generator());
+        let edwards = curves::edwards25519();
+        let k = BigUint::from(12345u32);
+        for w in [0, 1, 17] {
+            let err = Err(EccError::InvalidWindow {
+                width: w,
+                min: 2,
+                max: 16,
+            });
+            assert_eq!(ec.try_scalar_mul_vartime(g, &k, w), err);
+            // the affine fallback of CurveGroup
+            assert_eq!(
+                edwards
+                    .curve()
+                    .try_scalar_mul_vartime(edwards.generator(), &k, w),
+                err
+            );
+        }
+    }
+}
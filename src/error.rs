@@ -0,0 +1,55 @@
+use crate::elliptic_curve::Point;
+use num_bigint::BigUint;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum EccError {
+    #[error("Point {0} is not on curve")]
+    NotOnCurve(Point),
+    #[error("number: {value} is bigger or equal than modulus {modulus}")]
+    ScalarOutOfRange { value: BigUint, modulus: BigUint },
+    #[error("number: {value} has no inverse modulo {modulus}")]
+    NonInvertible { value: BigUint, modulus: BigUint },
+    #[error("Elements belong to different fields: modulus {left} and modulus {right}")]
+    FieldMismatch { left: BigUint, right: BigUint },
+    #[error("Points lie on different curves")]
+    CurveMismatch,
+    #[error("Unexpected point at infinity")]
+    IdentityPoint,
+    #[error("Nonce gives r = 0 or s = 0, sign again with another k")]
+    InvalidNonce,
+    #[error("Window width {width} is outside [{min}, {max}]")]
+    InvalidWindow {
+        width: usize,
+        min: usize,
+        max: usize,
+    },
+    #[error("Invalid encoding: {0}")]
+    InvalidEncoding(String),
+    #[error("Field modulus is not prime")]
+    ModulusNotPrime,
+    #[error("Reduction term x^{term} must be distinct and strictly between 1 and x^{degree}")]
+    InvalidReductionTerm { term: u64, degree: u64 },
+    #[error("Reduction polynomial is not irreducible")]
+    ReducibleModulus,
+    #[error("Curve is singular")]
+    SingularCurve,
+    #[error("Generator is not on the curve")]
+    GeneratorNotOnCurve,
+    #[error("Order times generator is not the identity")]
+    InvalidGeneratorOrder,
+    #[error("Group order is not prime")]
+    OrderNotPrime,
+    #[error("Cofactor times order is outside the Hasse bound")]
+    HasseBoundViolated,
+    #[error("Field of order {0} is too large to enumerate")]
+    FieldTooLarge(BigUint),
+    #[error("Point {0} is not in the prime-order subgroup")]
+    NotInSubgroup(Point),
+    #[error("Point order does not divide the given group order")]
+    InvalidPointOrder,
+    #[error("Endomorphism does not match the curve parameters")]
+    InvalidEndomorphism,
+}
+
+pub type Result<T> = std::result::Result<T, EccError>;
@@ -1,18 +1,78 @@
 use num_bigint::{BigUint, RandBigInt};
-use rand::{thread_rng, Rng};
+use rand::thread_rng;
+
+pub mod curves;
+pub mod ecdh;
+pub mod elliptic_curve;
+pub mod error;
+use elliptic_curve::{
+    check_wnaf_width, CurveGroup, DomainParameters, EllipticCurve, FiniteField, FixedBaseTable,
+    Point, DEFAULT_WNAF_WIDTH, FIXED_BASE_WINDOW,
+};
+pub use error::{EccError, Result};
+use std::sync::OnceLock;
+
+// Generic over the curve's group law so prime and binary curves share the
+// signing code; defaults to the short Weierstrass curve over F_p.
+#[allow(clippy::upper_case_acronyms)]
+pub struct ECDSA<C: CurveGroup = EllipticCurve> {
+    // validated curve, generator, order and cofactor
+    params: DomainParameters<C>,
+    // field of integers modulo the group order, where r and s live
+    scalar_field: FiniteField,
+    // wNAF window width for the public-scalar multiplications in verify
+    window: usize,
+    // multiples of the generator for k * G, built on first use
+    gen_table: OnceLock<FixedBaseTable>,
+}
 
-mod elliptic_curve;
-use elliptic_curve::{EllipticCurve, FiniteField, Point};
+impl<C: CurveGroup> ECDSA<C> {
+    // Only validated parameters are accepted, so construction cannot fail.
+    pub fn new(params: DomainParameters<C>) -> Self {
+        let scalar_field = FiniteField::new(params.order().clone());
+        ECDSA {
+            params,
+            scalar_field,
+            window: DEFAULT_WNAF_WIDTH,
+            gen_table: OnceLock::new(),
+        }
+    }
 
-struct ECDSA {
-    ec: EllipticCurve,
-    // group generator
-    gen: Point,
-    // group order
-    order: BigUint,
-}
+    // Wider windows trade a bigger precomputed table for fewer additions.
+    pub fn with_window(self, w: usize) -> Self {
+        self.try_with_window(w).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_with_window(mut self, w: usize) -> Result<Self> {
+        check_wnaf_width(w)?;
+        self.window = w;
+        Ok(self)
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    pub fn params(&self) -> &DomainParameters<C> {
+        &self.params
+    }
+
+    pub fn curve(&self) -> &C {
+        self.params.curve()
+    }
+
+    pub fn generator(&self) -> &Point {
+        self.params.generator()
+    }
+
+    pub fn order(&self) -> &BigUint {
+        self.params.order()
+    }
+
+    pub fn cofactor(&self) -> &BigUint {
+        self.params.cofactor()
+    }
 
-impl ECDSA {
     pub fn generate_key_pair(&self) -> (BigUint, Point) {
         let private_key = self.gen_private_key();
         let public_key = self.generate_public_key(&private_key);
@@ -20,130 +80,280 @@ impl ECDSA {
     }
 
     fn gen_private_key(&self) -> BigUint {
-        self.gen_random_n(&self.order)
+        self.gen_random_n(self.order())
     }
 
+    // random number in [1, max)
     fn gen_random_n(&self, max: &BigUint) -> BigUint {
         let mut rng = thread_rng();
-        rng.gen_biguint_range(&BigUint::from(0u32), max)
+        rng.gen_biguint_range(&BigUint::from(1u32), max)
     }
 
-    fn generate_public_key(&self, pk: &BigUint) -> Point {
-        self.ec.scalar_mul(&self.gen, pk)
+    pub fn generate_public_key(&self, pk: &BigUint) -> Point {
+        self.try_generate_public_key(pk)
+            .unwrap_or_else(|e| panic!("{}", e))
     }
 
-    pub fn generate_hash_less_than(data: &str, max: &BigUint) -> BigUint {
-        let digest = sha256::digest(data);
-        let hash_bytes = hex::decode(&digest).expect("Could not convert hash to Vec<u8>");
-        let hash = BigUint::from_bytes_be(&hash_bytes)
-            .modpow(&BigUint::from(1u32), &(max - BigUint::from(1u32)));
-        let hash = hash + BigUint::from(1u32);
-        hash
+    pub fn try_generate_public_key(&self, pk: &BigUint) -> Result<Point> {
+        self.check_scalar(pk)?;
+        self.secret_scalar_mul(pk)
     }
 
-    // R = k * G, r, _ = R(x , y)
+    // R = k * G, r = R(x) mod q
     // s = (hash(m) + private_key * r) * k^(-1) mod q
     pub fn sign(&self, hash: &BigUint, private_key: &BigUint, k: &BigUint) -> (BigUint, BigUint) {
-        assert!(hash < &self.order, "Hash is bigger than the order");
-        assert!(private_key < &self.order, "Hash is bigger than the order");
-        assert!(k < &self.order, "Hash is bigger than the order");
-
-        if let Point::Coordinates(r, _) = self.ec.scalar_mul(&self.gen, k) {
-            let ff = FiniteField {
-                p: self.order.clone(),
-            };
-            let s = ff.mul(&r, private_key);
-            let s = ff.add(&s, hash);
-            let k_inv = ff.inv_mul(k);
-            let s = ff.mul(&s, &k_inv);
-            return (r, s);
+        self.try_sign(hash, private_key, k)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_sign(
+        &self,
+        hash: &BigUint,
+        private_key: &BigUint,
+        k: &BigUint,
+    ) -> Result<(BigUint, BigUint)> {
+        self.check_hash(hash)?;
+        self.check_scalar(private_key)?;
+        self.check_scalar(k)?;
+
+        // r and s live in the scalar field of order n, not in the curve's base field
+        let scalar_field = &self.scalar_field;
+        match self.secret_scalar_mul(k)? {
+            Point::Coordinates(x, _) => {
+                let r = scalar_field.element(&x);
+                let s = (&r * scalar_field.element(private_key) + scalar_field.element(hash))
+                    .try_div(&scalar_field.element(k))?;
+                // verify rejects both, so the caller has to pick another k
+                if r.is_zero() || s.is_zero() {
+                    return Err(EccError::InvalidNonce);
+                }
+                Ok((r.value(), s.value()))
+            }
+            Point::Identity => Err(EccError::IdentityPoint),
         }
+    }
 
-        panic!("The random point R is Identity element")
+    // Returns false for malformed public keys and signatures instead of panicking.
+    pub fn verify(
+        &self,
+        hash: &BigUint,
+        public_key: &Point,
+        signature: &(BigUint, BigUint),
+    ) -> bool {
+        self.try_verify(hash, public_key, signature)
+            .unwrap_or(false)
     }
 
     // u1 = s^(-1) * hash(message) mod q
     // u2 = s^(-1) * r mod q
     // P = u1 G + u2 public_key = (x, y)
-    // if r == x then verified!
-    pub fn verify(
+    // if r == x mod q then verified!
+    pub fn try_verify(
         &self,
         hash: &BigUint,
         public_key: &Point,
         signature: &(BigUint, BigUint),
-    ) -> bool {
-        let ff = FiniteField {
-            p: self.order.clone(),
-        };
+    ) -> Result<bool> {
+        if public_key == &Point::Identity {
+            return Err(EccError::IdentityPoint);
+        }
+        if !self.curve().is_on_curve(public_key) {
+            return Err(EccError::NotOnCurve(public_key.clone()));
+        }
+        // with h > 1, a key outside <G> could leak information about the
+        // signer's nonce or make forgeries possible
+        if !self.params.is_in_subgroup(public_key) {
+            return Err(EccError::NotInSubgroup(public_key.clone()));
+        }
+
         let (r, s) = signature;
-        let s_inv = ff.inv_mul(s);
-        let u1 = ff.mul(&s_inv, hash);
-        let u2 = ff.mul(&s_inv, r);
-        let u1_point = self.ec.scalar_mul(&self.gen, &u1);
-        let u2_point = self.ec.scalar_mul(public_key, &u2);
-        if let Point::Coordinates(x, _) = self.ec.add(&u1_point, &u2_point) {
-            return x == *r;
+        self.check_scalar(r)?;
+        self.check_scalar(s)?;
+        let scalar_field = &self.scalar_field;
+        let s_inv = scalar_field.element(s).try_inv()?;
+        let u1 = (&s_inv * scalar_field.element(hash)).value();
+        let u2 = (&s_inv * scalar_field.element(r)).value();
+        // u1 G + u2 Q in one pass; everything here is public, so variable time is fine
+        let terms = [(self.generator().clone(), u1), (public_key.clone(), u2)];
+        let sum = self.curve().try_multi_scalar_mul(&terms, self.window)?;
+        if let Point::Coordinates(x, _) = sum {
+            return Ok(&scalar_field.element(&x).value() == r);
+        }
+        Ok(false)
+    }
+
+    // k * G for a secret k in [1, n). The generator table covers bits(n)
+    // bits, so every k costs the same operations: masked lookups and complete
+    // additions on EllipticCurve, the padded ladder on other curves.
+    fn secret_scalar_mul(&self, k: &BigUint) -> Result<Point> {
+        self.curve().try_fixed_base_mul(self.generator_table()?, k)
+    }
+
+    pub fn generator_table(&self) -> Result<&FixedBaseTable> {
+        if let Some(table) = self.gen_table.get() {
+            return Ok(table);
         }
-        return false;
+        let table = self.curve().fixed_base_table(
+            self.generator(),
+            self.order().bits(),
+            FIXED_BASE_WINDOW,
+        )?;
+        Ok(self.gen_table.get_or_init(|| table))
+    }
+
+    // A hash of 0 is fine, it only has to be reduced mod the order. verify
+    // takes any hash and reduces it.
+    fn check_hash(&self, hash: &BigUint) -> Result<()> {
+        if hash >= self.order() {
+            return Err(EccError::ScalarOutOfRange {
+                value: hash.clone(),
+                modulus: self.order().clone(),
+            });
+        }
+        Ok(())
+    }
+
+    // scalars used by ECDSA must lie in [1, order)
+    fn check_scalar(&self, n: &BigUint) -> Result<()> {
+        if n == &BigUint::from(0u32) || n >= self.order() {
+            return Err(EccError::ScalarOutOfRange {
+                value: n.clone(),
+                modulus: self.order().clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+// The message hash does not depend on the curve.
+impl ECDSA {
+    pub fn generate_hash_less_than(data: &str, max: &BigUint) -> BigUint {
+        Self::try_generate_hash_less_than(data, max).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_generate_hash_less_than(data: &str, max: &BigUint) -> Result<BigUint> {
+        if max <= &BigUint::from(1u32) {
+            return Err(EccError::ScalarOutOfRange {
+                value: BigUint::from(1u32),
+                modulus: max.clone(),
+            });
+        }
+        let digest = sha256::digest(data);
+        let hash_bytes =
+            hex::decode(&digest).map_err(|e| EccError::InvalidEncoding(e.to_string()))?;
+        let hash = BigUint::from_bytes_be(&hash_bytes)
+            .modpow(&BigUint::from(1u32), &(max - BigUint::from(1u32)));
+        Ok(hash + BigUint::from(1u32))
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::ECDSA;
-    use crate::elliptic_curve::{EllipticCurve, Point};
+    use super::{curves, EccError, ECDSA};
+    use crate::elliptic_curve::{BinaryCurve, BinaryField, DomainParameters, EllipticCurve, Point};
     use num_bigint::BigUint;
 
     fn get_test_ecdsa() -> ECDSA {
-        ECDSA {
-            ec: EllipticCurve {
-                a: BigUint::from(2u32),
-                b: BigUint::from(2u32),
-                p: BigUint::from(17u32),
-            },
-            gen: Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32)),
-            order: BigUint::from(19u32),
-        }
+        ECDSA::new(
+            DomainParameters::new(
+                EllipticCurve::new(
+                    BigUint::from(2u32),
+                    BigUint::from(2u32),
+                    BigUint::from(17u32),
+                ),
+                Point::Coordinates(BigUint::from(5u32), BigUint::from(1u32)),
+                BigUint::from(19u32),
+                BigUint::from(1u32),
+            )
+            .expect("could not validate test parameters"),
+        )
     }
 
     fn get_secp256k1_ec() -> ECDSA {
-        let p = BigUint::parse_bytes(
-            b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
-            16,
-        )
-        .expect("could not convert str to p");
-        let a = BigUint::parse_bytes(
-            b"0000000000000000000000000000000000000000000000000000000000000000",
+        ECDSA::new(curves::secp256k1().clone())
+    }
+
+    fn get_p256_ec() -> ECDSA {
+        ECDSA::new(curves::secp256r1().clone())
+    }
+
+    fn get_sect233k1_ec() -> ECDSA<BinaryCurve> {
+        let gx = BigUint::parse_bytes(
+            b"017232BA853A7E731AF129F22FF4149563A419C26BF50A4C9D6EEFAD6126",
             16,
         )
-        .expect("could not convert str to a");
-        let b = BigUint::parse_bytes(
-            b"0000000000000000000000000000000000000000000000000000000000000007",
+        .expect("could not convert str to gx");
+        let gy = BigUint::parse_bytes(
+            b"01DB537DECE819B7F70F555A67C427A8CD9BF18AEB9B56E0C11056FAE6A3",
             16,
         )
-        .expect("could not convert str to b");
+        .expect("could not convert str to gy");
         let n = BigUint::parse_bytes(
-            b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+            b"8000000000000000000000000000069D5BB915BCD46EFB1AD5F173ABDF",
             16,
         )
         .expect("could not convert str to n");
-        let gx = BigUint::parse_bytes(
-            b"79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
-            16,
-        )
-        .expect("could not convert str to gx");
-        let gy = BigUint::parse_bytes(
-            b"483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
-            16,
+
+        ECDSA::new(
+            DomainParameters::new(
+                BinaryCurve::new(
+                    BigUint::from(0u32),
+                    BigUint::from(1u32),
+                    BinaryField::trinomial(233, 74).expect("could not build the field"),
+                ),
+                Point::Coordinates(gx, gy),
+                n,
+                BigUint::from(4u32),
+            )
+            .expect("could not validate sect233k1 parameters"),
         )
-        .expect("could not convert str to gy");
+    }
 
-        ECDSA {
-            ec: EllipticCurve { a, b, p },
-            gen: Point::Coordinates(gx, gy),
-            order: n,
+    #[test]
+    fn test_public_key_matches_scalar_mul() {
+        let ecdsa = get_test_ecdsa();
+        for k in 1..19u32 {
+            let k = BigUint::from(k);
+            assert_eq!(
+                ecdsa.generate_public_key(&k),
+                ecdsa.curve().scalar_mul(ecdsa.generator(), &k)
+            );
         }
     }
+
+    #[test]
+    fn test_verify_window_widths() {
+        let msg = "Bob transferring 1 coin to Alice";
+        for w in 2..=8 {
+            let ecdsa = get_secp256k1_ec().with_window(w);
+            let (private_key, public_key) = ecdsa.generate_key_pair();
+            let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
+            let k = BigUint::from(0xC0FFEEu32 + w as u32);
+            let signature = ecdsa.sign(&hash, &private_key, &k);
+            assert!(ecdsa.verify(&hash, &public_key, &signature));
+        }
+    }
+
+    #[test]
+    fn test_invalid_window() {
+        for w in [0, 1, 17] {
+            assert_eq!(
+                get_test_ecdsa().try_with_window(w).err(),
+                Some(EccError::InvalidWindow {
+                    width: w,
+                    min: 2,
+                    max: 16
+                })
+            );
+        }
+        assert_eq!(
+            get_test_ecdsa()
+                .try_with_window(16)
+                .map(|ecdsa| ecdsa.window()),
+            Ok(16)
+        );
+    }
+
     #[test]
     fn test_sign_verify() {
         let ecdsa = get_test_ecdsa();
@@ -152,7 +362,7 @@ mod test {
         let public_key = ecdsa.generate_public_key(&private_key);
 
         let msg = "Bob transferring 1 coin to Alice";
-        let hash = ECDSA::generate_hash_less_than(msg, &ecdsa.order);
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
         let k = BigUint::from(18u32);
         let signature = ecdsa.sign(&hash, &private_key, &k);
 
@@ -168,12 +378,12 @@ mod test {
         let public_key = ecdsa.generate_public_key(&private_key);
 
         let msg = "Bob transferring 1 coin to Alice";
-        let hash = ECDSA::generate_hash_less_than(msg, &ecdsa.order);
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
         let k = BigUint::from(18u32);
         let signature = ecdsa.sign(&hash, &private_key, &k);
 
         let msg = "Bob transferring 100 coin to Alice";
-        let hash = ECDSA::generate_hash_less_than(msg, &ecdsa.order);
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
         let verify_result = ecdsa.verify(&hash, &public_key, &signature);
         assert!(!verify_result, "Verification is true")
     }
@@ -186,7 +396,7 @@ mod test {
         let public_key = ecdsa.generate_public_key(&private_key);
 
         let msg = "Bob transferring 1 coin to Alice";
-        let hash = ECDSA::generate_hash_less_than(msg, &ecdsa.order);
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
         let k = BigUint::from(18u32);
         let signature = ecdsa.sign(&hash, &private_key, &k);
 
@@ -195,6 +405,74 @@ mod test {
         let verify_result = ecdsa.verify(&hash, &public_key, &tempered_signature);
         assert!(!verify_result, "Verification is true")
     }
+    #[test]
+    fn test_sign_scalar_out_of_range() {
+        let ecdsa = get_test_ecdsa();
+
+        let msg = "Bob transferring 1 coin to Alice";
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
+        let result = ecdsa.try_sign(&hash, &BigUint::from(7u32), &BigUint::from(19u32));
+        assert_eq!(
+            result,
+            Err(EccError::ScalarOutOfRange {
+                value: BigUint::from(19u32),
+                modulus: BigUint::from(19u32)
+            })
+        );
+        assert!(ecdsa.try_generate_public_key(&BigUint::from(0u32)).is_err());
+    }
+
+    #[test]
+    fn test_sign_verify_zero_hash() {
+        let ecdsa = get_test_ecdsa();
+
+        let private_key = BigUint::from(7u32);
+        let public_key = ecdsa.generate_public_key(&private_key);
+        let hash = BigUint::from(0u32);
+        let signature = ecdsa.sign(&hash, &private_key, &BigUint::from(18u32));
+        assert!(ecdsa.verify(&hash, &public_key, &signature));
+        assert!(!ecdsa.verify(&BigUint::from(1u32), &public_key, &signature));
+    }
+
+    #[test]
+    fn test_sign_rejects_zero_r_or_s() {
+        let ecdsa = get_test_ecdsa();
+
+        // 7 * G = (0, 6), so r = 0
+        let result = ecdsa.try_sign(
+            &BigUint::from(9u32),
+            &BigUint::from(1u32),
+            &BigUint::from(7u32),
+        );
+        assert_eq!(result, Err(EccError::InvalidNonce));
+
+        // 3 * G = (10, 6), so s = (9 + 10 * 1) / 3 = 0 mod 19
+        let result = ecdsa.try_sign(
+            &BigUint::from(9u32),
+            &BigUint::from(1u32),
+            &BigUint::from(3u32),
+        );
+        assert_eq!(result, Err(EccError::InvalidNonce));
+    }
+
+    #[test]
+    fn test_verify_malformed_public_key() {
+        let ecdsa = get_test_ecdsa();
+
+        let private_key = BigUint::from(7u32);
+        let msg = "Bob transferring 1 coin to Alice";
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
+        let signature = ecdsa.sign(&hash, &private_key, &BigUint::from(18u32));
+
+        let off_curve = Point::Coordinates(BigUint::from(5u32), BigUint::from(2u32));
+        assert_eq!(
+            ecdsa.try_verify(&hash, &off_curve, &signature),
+            Err(EccError::NotOnCurve(off_curve.clone()))
+        );
+        assert!(!ecdsa.verify(&hash, &off_curve, &signature));
+        assert!(!ecdsa.verify(&hash, &Point::Identity, &signature));
+    }
+
     #[test]
     fn test_secp256k1_sign_verify() {
         let ecdsa = get_secp256k1_ec();
@@ -206,7 +484,7 @@ mod test {
         let public_key = ecdsa.generate_public_key(&private_key);
 
         let msg = "Bob transferring 1 coin to Alice";
-        let hash = ECDSA::generate_hash_less_than(msg, &ecdsa.order);
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
         let k = BigUint::parse_bytes(
             b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFAAAEBAAEDCE6AF48A03BBFD25E8CD0364141",
             16,
@@ -218,6 +496,26 @@ mod test {
         assert!(verify_result, "Verification is false")
     }
 
+    #[test]
+    fn test_p256_sign_verify() {
+        let ecdsa = get_p256_ec();
+        let (private_key, public_key) = ecdsa.generate_key_pair();
+
+        let msg = "Bob transferring 1 coin to Alice";
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
+        let k = BigUint::parse_bytes(
+            b"A6E3C57DD01ABE90086538398355DD4C3B17AA873382B0F24D6129493D8AAD60",
+            16,
+        )
+        .expect("could not convert str to k");
+        let signature = ecdsa.sign(&hash, &private_key, &k);
+
+        assert!(ecdsa.verify(&hash, &public_key, &signature));
+        let msg = "Bob transferring 100 coin to Alice";
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
+        assert!(!ecdsa.verify(&hash, &public_key, &signature));
+    }
+
     #[test]
     fn test_secp256k1_tempered_message() {
         let ecdsa = get_secp256k1_ec();
@@ -229,7 +527,7 @@ mod test {
         let public_key = ecdsa.generate_public_key(&private_key);
 
         let msg = "Bob transferring 1 coin to Alice";
-        let hash = ECDSA::generate_hash_less_than(msg, &ecdsa.order);
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
         let k = BigUint::parse_bytes(
             b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFAAAEBAAEDCE6AF48A03BBFD25E8CD0364141",
             16,
@@ -238,7 +536,7 @@ mod test {
         let signature = ecdsa.sign(&hash, &private_key, &k);
 
         let msg = "Bob transferring 100 coin to Alice";
-        let hash = ECDSA::generate_hash_less_than(msg, &ecdsa.order);
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
         let verify_result = ecdsa.verify(&hash, &public_key, &signature);
         assert!(!verify_result, "Verification is true")
     }
@@ -254,7 +552,7 @@ mod test {
         let public_key = ecdsa.generate_public_key(&private_key);
 
         let msg = "Bob transferring 1 coin to Alice";
-        let hash = ECDSA::generate_hash_less_than(msg, &ecdsa.order);
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
         let k = BigUint::parse_bytes(
             b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFAAAEBAAEDCE6AF48A03BBFD25E8CD0364141",
             16,
@@ -266,4 +564,100 @@ mod test {
         let verify_result = ecdsa.verify(&hash, &public_key, &tempered_signature);
         assert!(!verify_result, "Verification is true")
     }
+
+    #[test]
+    fn test_sect233k1_sign_verify() {
+        let ecdsa = get_sect233k1_ec();
+        let (private_key, public_key) = ecdsa.generate_key_pair();
+
+        let msg = "Bob transferring 1 coin to Alice";
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
+        let k = BigUint::parse_bytes(
+            b"3B17AA873382B0F24D6129493D8AAD60A6E3C57DD01ABE900865383983",
+            16,
+        )
+        .expect("could not convert str to k");
+        let signature = ecdsa.sign(&hash, &private_key, &k);
+
+        assert!(ecdsa.verify(&hash, &public_key, &signature));
+        let msg = "Bob transferring 100 coin to Alice";
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
+        assert!(!ecdsa.verify(&hash, &public_key, &signature));
+    }
+
+    #[test]
+    fn test_secret_scalar_mul_without_masked_lookup() {
+        // binary and twisted Edwards curves have no masked table lookup, so
+        // k * G falls back to the padded ladder; it must still give k * G
+        let msg = "Bob transferring 1 coin to Alice";
+        let k = BigUint::parse_bytes(
+            b"3B17AA873382B0F24D6129493D8AAD60A6E3C57DD01ABE900865383983",
+            16,
+        )
+        .expect("could not convert str to k");
+
+        let ecdsa = get_sect233k1_ec();
+        let (private_key, public_key) = ecdsa.generate_key_pair();
+        let g = ecdsa.generator();
+        assert_eq!(
+            ecdsa.curve().try_scalar_mul(g, &private_key),
+            Ok(public_key.clone())
+        );
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
+        let (r, s) = ecdsa.sign(&hash, &private_key, &k);
+        match ecdsa.curve().try_scalar_mul(g, &k) {
+            Ok(Point::Coordinates(x, _)) => assert_eq!(r, x % ecdsa.order()),
+            other => panic!("unexpected k * G: {:?}", other),
+        }
+        assert!(ecdsa.verify(&hash, &public_key, &(r, s)));
+
+        let ecdsa = ECDSA::new(curves::edwards25519().clone());
+        let (private_key, public_key) = ecdsa.generate_key_pair();
+        let g = ecdsa.generator();
+        assert_eq!(
+            ecdsa.curve().try_scalar_mul(g, &private_key),
+            Ok(public_key.clone())
+        );
+        let hash = ECDSA::generate_hash_less_than(msg, ecdsa.order());
+        let signature = ecdsa.sign(&hash, &private_key, &k);
+        assert!(ecdsa.verify(&hash, &public_key, &signature));
+    }
+
+    #[test]
+    fn test_verify_rejects_key_outside_subgroup() {
+        // y^2 = x^3 + 2x + 3 over F_17 has 22 = 2 * 11 points
+        let ecdsa = ECDSA::new(
+            DomainParameters::new(
+                EllipticCurve::new(
+                    BigUint::from(2u32),
+                    BigUint::from(3u32),
+                    BigUint::from(17u32),
+                ),
+                Point::Coordinates(BigUint::from(3u32), BigUint::from(6u32)),
+                BigUint::from(11u32),
+                BigUint::from(2u32),
+            )
+            .expect("could not validate test parameters"),
+        );
+        let hash = BigUint::from(7u32);
+        // 3G = (15, 5), so r = 4 and d = 1 would give s = 0; d = 2 gives s = 5
+        let private_key = BigUint::from(2u32);
+        let public_key = ecdsa.generate_public_key(&private_key);
+        let signature = ecdsa
+            .try_sign(&hash, &private_key, &BigUint::from(3u32))
+            .expect("d = 2 and k = 3 give r = 4, s = 5");
+        assert_eq!(signature, (BigUint::from(4u32), BigUint::from(5u32)));
+        assert!(ecdsa.verify(&hash, &public_key, &signature));
+
+        // (2, 7) has order 22 and (16, 0) order 2
+        for key in [
+            Point::Coordinates(BigUint::from(2u32), BigUint::from(7u32)),
+            Point::Coordinates(BigUint::from(16u32), BigUint::from(0u32)),
+        ] {
+            assert_eq!(
+                ecdsa.try_verify(&hash, &key, &signature),
+                Err(EccError::NotInSubgroup(key.clone()))
+            );
+        }
+    }
 }